@@ -3,10 +3,39 @@ extern crate criterion;
 extern crate fuzzy_dbscan;
 extern crate utils;
 
+use fuzzy_dbscan::index::SpatialIndex;
 use fuzzy_dbscan::*;
 use utils::*;
 
-use criterion::Criterion;
+use criterion::{BenchmarkId, Criterion};
+use std::collections::HashSet;
+
+/// A brute-force index that collects neighbors in a hash set before sorting them, as
+/// `region_query` did before returning a sorted `Vec`.
+struct HashSetIndex {
+    eps: f64,
+}
+
+impl SpatialIndex<Point> for HashSetIndex {
+    fn build(_points: &[Point], eps: f64) -> Self {
+        HashSetIndex { eps }
+    }
+
+    fn region_query(&self, points: &[Point], point_index: usize) -> Vec<usize> {
+        let neighbors: HashSet<usize> = points
+            .iter()
+            .enumerate()
+            .filter(|&(neighbor_index, neighbor_point)| {
+                neighbor_index != point_index
+                    && neighbor_point.distance(&points[point_index]) <= self.eps
+            })
+            .map(|(neighbor_index, _)| neighbor_index)
+            .collect();
+        let mut neighbors: Vec<usize> = neighbors.into_iter().collect();
+        neighbors.sort_unstable();
+        neighbors
+    }
+}
 
 fn bench_lib(c: &mut Criterion) {
     c.bench_function("100 points", |b| {
@@ -19,10 +48,168 @@ fn bench_lib(c: &mut Criterion) {
             eps_max: 20.0,
             pts_min: 50.0,
             pts_max: 50.0,
+            ..Default::default()
         };
         b.iter(|| fuzzy_dbscan.cluster(&points))
     });
 }
 
-criterion_group!(benches, bench_lib);
+fn bench_index(c: &mut Criterion) {
+    let points = flat_vec![
+        gaussian_circle(2500, 0.0, 0.0, 100.0),
+        gaussian_circle(2500, 500.0, 0.0, 100.0),
+    ];
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 5.0,
+        eps_max: 10.0,
+        pts_min: 10.0,
+        pts_max: 20.0,
+        ..Default::default()
+    };
+    let mut group = c.benchmark_group("5000 points");
+    group.sample_size(10);
+    group.bench_function("brute force", |b| {
+        b.iter(|| fuzzy_dbscan.cluster_with_index::<index::BruteForce, _>(&points))
+    });
+    group.bench_function("bounding box", |b| {
+        b.iter(|| fuzzy_dbscan.cluster_with_index::<index::BoundingBoxIndex<2>, _>(&points))
+    });
+    group.bench_function("grid", |b| {
+        b.iter(|| fuzzy_dbscan.cluster_with_index::<index::GridIndex, _>(&points))
+    });
+    #[cfg(feature = "kiddo")]
+    group.bench_function("kd-tree", |b| {
+        b.iter(|| fuzzy_dbscan.cluster_with_index::<index::KdTree<2>, _>(&points))
+    });
+    group.finish();
+}
+
+fn bench_large(c: &mut Criterion) {
+    // Compare runs with and without the `rayon` feature to measure the parallel speedup.
+    let points = flat_vec![
+        gaussian_circle(5000, 0.0, 0.0, 100.0),
+        gaussian_circle(5000, 500.0, 0.0, 100.0),
+    ];
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 5.0,
+        eps_max: 10.0,
+        pts_min: 20.0,
+        pts_max: 40.0,
+        ..Default::default()
+    };
+    let mut group = c.benchmark_group("10000 points");
+    group.sample_size(10);
+    group.bench_function("brute force", |b| b.iter(|| fuzzy_dbscan.cluster(&points)));
+    group.finish();
+}
+
+fn bench_scaling(c: &mut Criterion) {
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 5.0,
+        eps_max: 10.0,
+        pts_min: 10.0,
+        pts_max: 20.0,
+        ..Default::default()
+    };
+    let mut group = c.benchmark_group("region query");
+    group.sample_size(10);
+    for n in [100, 1_000, 10_000, 100_000] {
+        // Scale the radius to keep the density of the 5000 points benchmark.
+        let r = 100.0 * (n as f64 / 5000.0).sqrt();
+        let points = flat_vec![
+            gaussian_circle(n / 2, 0.0, 0.0, r),
+            gaussian_circle(n / 2, r * 5.0, 0.0, r),
+        ];
+        // Brute force takes minutes for 100000 points, thus only benchmark the KD-tree there.
+        if n <= 10_000 {
+            group.bench_with_input(BenchmarkId::new("hash set", n), &points, |b, points| {
+                b.iter(|| fuzzy_dbscan.cluster_with_index::<HashSetIndex, _>(points))
+            });
+            group.bench_with_input(BenchmarkId::new("sorted vec", n), &points, |b, points| {
+                b.iter(|| fuzzy_dbscan.cluster_with_index::<index::BruteForce, _>(points))
+            });
+        }
+        #[cfg(feature = "kiddo")]
+        group.bench_with_input(BenchmarkId::new("kd-tree", n), &points, |b, points| {
+            b.iter(|| fuzzy_dbscan.cluster_with_index::<index::KdTree<2>, _>(points))
+        });
+    }
+    group.finish();
+}
+
+fn bench_arrays(c: &mut Criterion) {
+    // Spread 2D points over 128 dimensions to measure the cost of high-dimensional distances.
+    let points: Vec<[f32; 128]> = flat_vec![
+        gaussian_circle(500, 0.0, 0.0, 10.0),
+        gaussian_circle(500, 50.0, 0.0, 10.0),
+    ]
+    .iter()
+    .map(|point| {
+        let [x, y] = point.coordinates();
+        let mut array = [0.0; 128];
+        for (i, coordinate) in array.iter_mut().enumerate() {
+            *coordinate = (if i % 2 == 0 { x } else { y }) as f32 / 8.0;
+        }
+        array
+    })
+    .collect();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 2.0,
+        eps_max: 4.0,
+        pts_min: 10.0,
+        pts_max: 20.0,
+        ..Default::default()
+    };
+    c.bench_function("1000 points with 128 dimensions", |b| {
+        b.iter(|| fuzzy_dbscan.cluster(&points))
+    });
+}
+
+fn bench_compressed(c: &mut Criterion) {
+    // Ten clusters of sequential points with every tenth point as a border point.
+    let clusters: Vec<Cluster> = (0..10)
+        .map(|cluster_id| {
+            (cluster_id * 10_000..(cluster_id + 1) * 10_000)
+                .map(|index| Assignment {
+                    index,
+                    cluster_id: Some(cluster_id),
+                    label: if index % 10 == 0 { 0.5 } else { 1.0 },
+                    category: if index % 10 == 0 {
+                        Category::Border
+                    } else {
+                        Category::Core
+                    },
+                })
+                .collect()
+        })
+        .collect();
+    let compressed = compress_clusters(clusters.clone());
+    let compressed_bytes: usize = compressed
+        .iter()
+        .map(CompressedCluster::size_in_bytes)
+        .sum();
+    println!(
+        "100000 assignments: {} bytes, compressed: {} bytes",
+        100_000 * std::mem::size_of::<Assignment>(),
+        compressed_bytes
+    );
+    let mut group = c.benchmark_group("100000 assignments");
+    group.bench_function("compress", |b| {
+        b.iter(|| compress_clusters(clusters.clone()))
+    });
+    group.bench_function("decompress", |b| {
+        b.iter(|| decompress_clusters::<f64>(compressed.clone()))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_lib,
+    bench_index,
+    bench_large,
+    bench_scaling,
+    bench_arrays,
+    bench_compressed
+);
 criterion_main!(benches);
@@ -0,0 +1,86 @@
+#ifndef FUZZY_DBSCAN_H
+#define FUZZY_DBSCAN_H
+
+/* This file is generated by cbindgen, do not edit it manually. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * An instance of the FuzzyDBSCAN algorithm with parameters of scalar type `S`.
+ *
+ * Note that when setting `eps_min = eps_max` and `pts_min = pts_max` the algorithm will reduce to classic DBSCAN,
+ * see [`ClusteringMode`].
+ */
+typedef struct FuzzyDBSCAN FuzzyDBSCAN;
+
+/**
+ * An assignment of a point to a cluster for C.
+ */
+typedef struct FfiAssignment {
+  /**
+   * The point index.
+   */
+  size_t index;
+  /**
+   * The cluster identifier, or `-1` for noise.
+   */
+  int64_t cluster_id;
+  /**
+   * The soft label between `0.0` and `1.0`.
+   */
+  float label;
+  /**
+   * The category, which is `0` (core), `1` (border), or `2` (noise).
+   */
+  uint8_t category;
+} FfiAssignment;
+
+/**
+ * Creates an instance of the FuzzyDBSCAN algorithm with the given parameters and the defaults
+ * otherwise, or returns null if the parameters are invalid.
+ *
+ * The instance must be freed by [`fuzzy_dbscan_free`].
+ */
+struct FuzzyDBSCAN *fuzzy_dbscan_create(float eps_min, float eps_max, float pts_min, float pts_max);
+
+/**
+ * Frees an instance created by [`fuzzy_dbscan_create`], which may be null.
+ *
+ * # Safety
+ *
+ * `fuzzy_dbscan` must be null or returned by [`fuzzy_dbscan_create`], and must not be used
+ * afterwards.
+ */
+void fuzzy_dbscan_free(struct FuzzyDBSCAN *fuzzy_dbscan);
+
+/**
+ * Clusters `n_points` points whose `dimensions` coordinates are stored consecutively in
+ * `points`, e.g., `[x0, y0, x1, y1, ...]` for 2D points.
+ *
+ * Returns the assignments of all clusters, followed by the noise points, and writes their
+ * number to `n_assignments`. Returns null and writes zero if clustering fails. The assignments
+ * must be freed by [`fuzzy_dbscan_free_result`].
+ *
+ * # Safety
+ *
+ * `fuzzy_dbscan` must be returned by [`fuzzy_dbscan_create`], `points` must point to
+ * `n_points * dimensions` floats, and `n_assignments` must be valid for writes.
+ */
+struct FfiAssignment *fuzzy_dbscan_cluster(const struct FuzzyDBSCAN *fuzzy_dbscan,
+                                           const float *points,
+                                           size_t n_points,
+                                           size_t dimensions,
+                                           size_t *n_assignments);
+
+/**
+ * Frees `n_assignments` assignments returned by [`fuzzy_dbscan_cluster`], which may be null.
+ *
+ * # Safety
+ *
+ * `assignments` must be null or returned by [`fuzzy_dbscan_cluster`] along with
+ * `n_assignments`, and must not be used afterwards.
+ */
+void fuzzy_dbscan_free_result(struct FfiAssignment *assignments, size_t n_assignments);
+
+#endif  /* FUZZY_DBSCAN_H */
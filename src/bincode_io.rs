@@ -0,0 +1,23 @@
+//! Reading and writing clusters as bincode.
+use alloc::vec::Vec;
+use bincode::Options;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::Cluster;
+
+/// The error type of reading or writing bincode.
+pub type BincodeError = bincode::Error;
+
+/// Encodes `clusters` as compact binary data, where integers are variable-length encoded.
+pub fn clusters_to_bincode<S: Serialize>(clusters: &[Cluster<S>]) -> Vec<u8> {
+    // Serializing into memory only fails for unsupported types, which clusters do not contain.
+    bincode::DefaultOptions::new().serialize(clusters).unwrap()
+}
+
+/// Decodes clusters encoded by [`clusters_to_bincode`] from `data`.
+pub fn clusters_from_bincode<S: DeserializeOwned>(
+    data: &[u8],
+) -> Result<Vec<Cluster<S>>, BincodeError> {
+    bincode::DefaultOptions::new().deserialize(data)
+}
@@ -0,0 +1,97 @@
+//! Classification of new points against an existing clustering.
+use alloc::vec;
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::{Assignment, BorderLabelMode, Category, Cluster, FuzzyDBSCAN, MetricSpace};
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Computes the fuzzy density of an arbitrary `query` point as if it were added to the
+    /// `dataset`, i.e., one (unless `self_included_in_density` is `false`) plus the sum of the
+    /// distance memberships of all points of the `dataset` within `eps_max` (or the number of
+    /// points within `eps_min`, depending on the [density mode](crate::DensityMode)).
+    pub fn density_at<P: MetricSpace<S>>(&self, query: &P, dataset: &[P]) -> S {
+        dataset
+            .iter()
+            .map(|point| {
+                let distance = query.distance(point);
+                self.density_membership(distance, self.mu_of_distance(distance))
+            })
+            .fold(self.self_density(), |density, membership| {
+                density + membership
+            })
+    }
+
+    /// Classifies a new `query` point against the `clusters` of a `dataset` without
+    /// reclustering, as if the point were appended to the `dataset`.
+    ///
+    /// A core point joins the cluster of its closest core point, and a border point joins the
+    /// cluster in which it gets the highest label. A core point without any core points in its
+    /// neighborhood would start a new cluster, thus it has no `cluster_id`, just like noise. The
+    /// densities of the clustered points are not updated.
+    pub fn classify_point<P: MetricSpace<S>>(
+        &self,
+        query: &P,
+        clusters: &[Cluster<S>],
+        dataset: &[P],
+    ) -> Assignment<S> {
+        // The cluster and core label of every core point.
+        let mut cores: Vec<Option<(usize, S)>> = vec![None; dataset.len()];
+        for (cluster_id, cluster) in clusters.iter().enumerate() {
+            for a in cluster.iter().filter(|a| a.category == Category::Core) {
+                cores[a.index] = Some((cluster_id, a.label));
+            }
+        }
+        let core_label = self.mu_min_p(self.density_at(query, dataset));
+        let mut closest_core: Option<(usize, S)> = None;
+        // The label of the query point as a border point of each cluster.
+        let mut border_labels: Vec<Option<S>> = vec![None; clusters.len()];
+        for (point, core) in dataset.iter().zip(&cores) {
+            let (cluster_id, label) = match core {
+                Some(core) => *core,
+                None => continue,
+            };
+            let mu_distance = self.mu_distance(query, point);
+            if mu_distance <= S::zero() {
+                continue;
+            }
+            if closest_core.map_or(true, |(_, closest)| mu_distance > closest) {
+                closest_core = Some((cluster_id, mu_distance));
+            }
+            let contribution = label.min(mu_distance);
+            border_labels[cluster_id] =
+                Some(match (border_labels[cluster_id], self.border_label_mode) {
+                    (None, _) => contribution,
+                    (Some(label), BorderLabelMode::MinCore) => label.min(contribution),
+                    (Some(label), BorderLabelMode::MaxCore) => label.max(contribution),
+                });
+        }
+        let (cluster_id, label, category) = if self.is_core_label(core_label) {
+            let cluster_id = closest_core.map(|(cluster_id, _)| cluster_id);
+            (cluster_id, core_label, Category::Core)
+        } else {
+            let border = border_labels
+                .iter()
+                .enumerate()
+                .filter_map(|(cluster_id, label)| label.map(|label| (cluster_id, label)))
+                .filter(|&(_, label)| label >= self.border_threshold)
+                .fold(
+                    None,
+                    |best: Option<(usize, S)>, (cluster_id, label)| match best {
+                        Some((_, best_label)) if best_label >= label => best,
+                        _ => Some((cluster_id, label)),
+                    },
+                );
+            match border {
+                Some((cluster_id, label)) => (Some(cluster_id), label, Category::Border),
+                None => (None, S::one(), Category::Noise),
+            }
+        };
+        Assignment {
+            index: dataset.len(),
+            cluster_id,
+            label,
+            category,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Incremental clustering.
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::{Assignment, Cluster, ClusterState, FuzzyDBSCAN, FuzzyDBSCANError, MetricSpace};
+
+/// A change of the clustering caused by [adding](Clusterer::add_point) a point or
+/// [updating](FuzzyDBSCAN::update) a state.
+#[derive(Debug, Clone)]
+pub enum ClusterUpdate<S = f64> {
+    /// The added point has been assigned to the cluster `cluster_id`.
+    PointAddedToCluster {
+        /// The cluster identifier.
+        cluster_id: usize,
+        /// The assignment of the added point.
+        assignment: Assignment<S>,
+    },
+    /// The clusters `ids` have been merged into the cluster with the first identifier.
+    ClustersWereMerged {
+        /// The cluster identifiers in ascending order.
+        ids: Vec<usize>,
+    },
+    /// A new cluster `cluster_id` has been created.
+    NewClusterCreated {
+        /// The cluster identifier.
+        cluster_id: usize,
+    },
+    /// The cluster `cluster_id` has grown without being merged, which is only reported by
+    /// [updates](FuzzyDBSCAN::update).
+    ClusterExpanded {
+        /// The cluster identifier.
+        cluster_id: usize,
+    },
+}
+
+/// A stateful instance of the FuzzyDBSCAN algorithm, which clusters points one at a time.
+///
+/// Adding a point only queries its neighborhood and expands the clusters whose cores have gained
+/// neighbors, see [`FuzzyDBSCAN::update`]. Clusters keep their identifier until they are merged
+/// and are kept regardless of `min_cluster_size`, as they may still grow. Note that noise is not a
+/// cluster, thus points classified as noise do not cause any updates.
+pub struct Clusterer<P, S = f64> {
+    fuzzy_dbscan: FuzzyDBSCAN<S>,
+    state: ClusterState<P, S>,
+}
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Creates a [clusterer](Clusterer) that has clustered the `initial_points`, which may be
+    /// empty.
+    pub fn into_clusterer<P: MetricSpace<S>>(
+        self,
+        initial_points: Vec<P>,
+    ) -> Result<Clusterer<P, S>, FuzzyDBSCANError> {
+        self.validate()?;
+        let mut state = ClusterState::new();
+        self.extend_state(&mut state, initial_points);
+        Ok(Clusterer {
+            fuzzy_dbscan: self,
+            state,
+        })
+    }
+}
+
+impl<P: MetricSpace<S>, S: Float + Send + Sync> Clusterer<P, S> {
+    /// Adds a `point` and returns how the clustering changed.
+    pub fn add_point(&mut self, point: P) -> Vec<ClusterUpdate<S>> {
+        let mut updates = self.fuzzy_dbscan.extend_state(&mut self.state, [point]);
+        updates.retain(|update| !matches!(update, ClusterUpdate::ClusterExpanded { .. }));
+        updates
+    }
+
+    /// Returns all points added so far.
+    pub fn points(&self) -> &[P] {
+        self.state.points()
+    }
+
+    /// Returns the clusters and their identifiers.
+    pub fn clusters(&self) -> &[(usize, Cluster<S>)] {
+        self.state.clusters()
+    }
+
+    /// Returns the points classified as noise.
+    pub fn noise(&self) -> &Cluster<S> {
+        self.state.noise()
+    }
+}
@@ -0,0 +1,101 @@
+//! Compact representation of clusters.
+use alloc::vec::Vec;
+use core::mem::size_of;
+use num_traits::Float;
+
+use crate::{Assignment, Category, Cluster};
+
+/// A cluster whose assignments are sorted by point index, which is delta-coded, and whose
+/// labels are quantized to 256 levels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompressedCluster {
+    /// The cluster identifier shared by all assignments.
+    pub cluster_id: Option<usize>,
+    /// The difference of each point index to the previous one, starting at zero.
+    pub index_deltas: Vec<u32>,
+    /// The labels quantized as `round(label * 255)`.
+    pub labels: Vec<u8>,
+    /// The categories.
+    pub categories: Vec<Category>,
+}
+
+impl CompressedCluster {
+    /// Returns the number of assignments.
+    pub fn len(&self) -> usize {
+        self.index_deltas.len()
+    }
+
+    /// Returns whether there are no assignments.
+    pub fn is_empty(&self) -> bool {
+        self.index_deltas.is_empty()
+    }
+
+    /// Returns the number of bytes used by the assignments.
+    pub fn size_in_bytes(&self) -> usize {
+        self.len() * (size_of::<u32>() + size_of::<u8>() + size_of::<Category>())
+    }
+}
+
+/// Compresses `clusters`, which keep their order, while their assignments are sorted by point
+/// index.
+///
+/// Each cluster takes the identifier of its first assignment. Panics if point indices within a
+/// cluster are more than `u32::MAX` apart.
+pub fn compress_clusters<S: Float>(clusters: Vec<Cluster<S>>) -> Vec<CompressedCluster> {
+    clusters
+        .into_iter()
+        .map(|mut cluster| {
+            cluster.sort_by_key(|a| a.index);
+            let mut previous_index = 0;
+            let index_deltas = cluster
+                .iter()
+                .map(|a| {
+                    let delta = u32::try_from(a.index - previous_index)
+                        .expect("point indices must be at most u32::MAX apart");
+                    previous_index = a.index;
+                    delta
+                })
+                .collect();
+            let labels = cluster
+                .iter()
+                .map(|a| {
+                    let quantized =
+                        (a.label.max(S::zero()).min(S::one()) * S::from(255).unwrap()).round();
+                    quantized.to_u8().unwrap_or(0)
+                })
+                .collect();
+            CompressedCluster {
+                cluster_id: cluster.first().and_then(|a| a.cluster_id),
+                index_deltas,
+                labels,
+                categories: cluster.into_iter().map(|a| a.category).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Restores clusters compressed by [`compress_clusters`], whose labels are rounded to the
+/// nearest multiple of `1/255`.
+pub fn decompress_clusters<S: Float>(compressed: Vec<CompressedCluster>) -> Vec<Cluster<S>> {
+    compressed
+        .into_iter()
+        .map(|cluster| {
+            let mut index = 0;
+            cluster
+                .index_deltas
+                .iter()
+                .zip(&cluster.labels)
+                .zip(cluster.categories)
+                .map(|((&delta, &label), category)| {
+                    index += delta as usize;
+                    Assignment {
+                        index,
+                        cluster_id: cluster.cluster_id,
+                        label: S::from(label).unwrap() / S::from(255).unwrap(),
+                        category,
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,168 @@
+//! Boundaries of fuzzy clusters.
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::{Category, Cluster, FuzzyDBSCAN, MetricSpace, Point2D};
+
+/// A grid edge given by its orientation and lower grid node, i.e., `(vertical, i, j)`.
+type Edge = (bool, usize, usize);
+
+/// The maximum number of grid nodes to sample, which limits the memory of the labels to 64 MiB.
+const MAX_GRID_NODES: usize = 1 << 24;
+
+impl FuzzyDBSCAN<f32> {
+    /// Approximates the boundary of the region of a `cluster` of 2D `points` in which the label
+    /// is at least `threshold` by a polygon, e.g., to draw the cluster.
+    ///
+    /// The label at any position is the highest label that it would get as a border point of any
+    /// member, i.e., the minimum of the member's label and their distance membership. The label is
+    /// sampled on a grid with a spacing of `resolution` and the boundary is traced by marching
+    /// squares, resolving ambiguous cells by the mean of their corners. If the region has several
+    /// boundaries, e.g., holes or separate parts, the one with the most vertices is returned.
+    /// Returns an empty polygon if no member has a label of at least `threshold`, if `threshold`
+    /// is not positive, if `resolution` is not positive, or if the grid would have more than
+    /// 2<sup>24</sup> nodes, i.e., if `resolution` is too small for the extent of the cluster.
+    pub fn isocontour(
+        &self,
+        points: &[Point2D],
+        cluster: &Cluster<f32>,
+        threshold: f32,
+        resolution: f32,
+    ) -> Vec<(f32, f32)> {
+        let members: Vec<_> = cluster
+            .iter()
+            .filter(|a| a.category != Category::Noise && a.label >= threshold)
+            .map(|a| (points[a.index], a.label))
+            .collect();
+        if members.is_empty() || threshold <= 0.0 || resolution.is_nan() || resolution <= 0.0 {
+            return Vec::new();
+        }
+        // Pad the grid beyond eps_max, such that its outermost nodes are outside of the region
+        // and all boundaries are closed.
+        let padding = self.eps_max + resolution;
+        let (min_x, min_y, max_x, max_y) = members.iter().fold(
+            (f32::MAX, f32::MAX, f32::MIN, f32::MIN),
+            |(min_x, min_y, max_x, max_y), (point, _)| {
+                (
+                    min_x.min(point.x),
+                    min_y.min(point.y),
+                    max_x.max(point.x),
+                    max_y.max(point.y),
+                )
+            },
+        );
+        let (origin_x, origin_y) = (min_x - padding, min_y - padding);
+        let columns = ((max_x - min_x + 2.0 * padding) / resolution).ceil() + 1.0;
+        let rows = ((max_y - min_y + 2.0 * padding) / resolution).ceil() + 1.0;
+        let nodes = f64::from(columns) * f64::from(rows);
+        if nodes.is_nan() || nodes > MAX_GRID_NODES as f64 {
+            return Vec::new();
+        }
+        let (columns, rows) = (columns as usize, rows as usize);
+        let position = |i: usize, j: usize| {
+            (
+                origin_x + i as f32 * resolution,
+                origin_y + j as f32 * resolution,
+            )
+        };
+        let mut labels = vec![0.0; columns * rows];
+        for j in 0..rows {
+            for i in 0..columns {
+                let (x, y) = position(i, j);
+                let sample = Point2D::new(x, y);
+                labels[j * columns + i] = members.iter().fold(0.0, |label, (point, core_label)| {
+                    let mu_distance = self.mu_of_distance(sample.distance(point));
+                    label.max(core_label.min(mu_distance))
+                });
+            }
+        }
+        let label = |i: usize, j: usize| labels[j * columns + i];
+        let inside = |i: usize, j: usize| label(i, j) >= threshold;
+        // The segments of each cell, connecting the edges where the boundary crosses.
+        let mut segments: Vec<(Edge, Edge)> = Vec::new();
+        for j in 0..rows - 1 {
+            for i in 0..columns - 1 {
+                let (bottom, right, top, left) = (
+                    (false, i, j),
+                    (true, i + 1, j),
+                    (false, i, j + 1),
+                    (true, i, j),
+                );
+                let crossed = [
+                    (bottom, inside(i, j) != inside(i + 1, j)),
+                    (right, inside(i + 1, j) != inside(i + 1, j + 1)),
+                    (top, inside(i, j + 1) != inside(i + 1, j + 1)),
+                    (left, inside(i, j) != inside(i, j + 1)),
+                ];
+                let edges: Vec<Edge> = crossed
+                    .iter()
+                    .filter(|(_, crossed)| *crossed)
+                    .map(|(edge, _)| *edge)
+                    .collect();
+                match edges.len() {
+                    2 => segments.push((edges[0], edges[1])),
+                    4 => {
+                        let center =
+                            (label(i, j) + label(i + 1, j) + label(i, j + 1) + label(i + 1, j + 1))
+                                / 4.0;
+                        // Separate the corners that are not connected through the center.
+                        if inside(i, j) == (center >= threshold) {
+                            segments.push((bottom, right));
+                            segments.push((top, left));
+                        } else {
+                            segments.push((left, bottom));
+                            segments.push((right, top));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let vertex = |(vertical, i, j): Edge| {
+            let (i_b, j_b) = if vertical { (i, j + 1) } else { (i + 1, j) };
+            let (label_a, label_b) = (label(i, j), label(i_b, j_b));
+            let t = (threshold - label_a) / (label_b - label_a);
+            let ((x_a, y_a), (x_b, y_b)) = (position(i, j), position(i_b, j_b));
+            (x_a + t * (x_b - x_a), y_a + t * (y_b - y_a))
+        };
+        // Every crossed edge is shared by exactly two segments of a closed boundary.
+        let mut edge_segments: BTreeMap<Edge, Vec<usize>> = BTreeMap::new();
+        for (segment_index, &(a, b)) in segments.iter().enumerate() {
+            edge_segments.entry(a).or_default().push(segment_index);
+            edge_segments.entry(b).or_default().push(segment_index);
+        }
+        let mut used = vec![false; segments.len()];
+        let mut longest = Vec::new();
+        for start in 0..segments.len() {
+            if used[start] {
+                continue;
+            }
+            let mut polygon = Vec::new();
+            let (first, mut edge) = segments[start];
+            let mut segment_index = start;
+            used[start] = true;
+            polygon.push(vertex(first));
+            while edge != first {
+                polygon.push(vertex(edge));
+                let next = edge_segments[&edge]
+                    .iter()
+                    .copied()
+                    .find(|&next| next != segment_index && !used[next]);
+                let next = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+                used[next] = true;
+                segment_index = next;
+                let (a, b) = segments[next];
+                edge = if a == edge { b } else { a };
+            }
+            if polygon.len() > longest.len() {
+                longest = polygon;
+            }
+        }
+        longest
+    }
+}
@@ -0,0 +1,77 @@
+//! Reading and writing clusters as CSV.
+use alloc::vec::Vec;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::{Read, Write};
+
+use crate::{Assignment, Category, Cluster};
+
+/// The error type of reading or writing CSV.
+pub type CsvError = csv::Error;
+
+/// A CSV row, where noise points have a `cluster_id` of `-1`.
+#[derive(Serialize, Deserialize)]
+struct Row<S> {
+    cluster_id: i64,
+    point_index: usize,
+    label: S,
+    category: Category,
+}
+
+/// Writes `clusters` to `writer` as CSV with one row per assignment and the columns
+/// `cluster_id`, `point_index`, `label`, and `category`.
+///
+/// The `cluster_id` is the position in `clusters`, except for noise points, which use `-1`.
+pub fn write_clusters_csv<W: Write, S: Copy + Serialize>(
+    writer: W,
+    clusters: &[Cluster<S>],
+) -> Result<(), CsvError> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for (cluster_id, cluster) in clusters.iter().enumerate() {
+        for a in cluster {
+            writer.serialize(Row {
+                cluster_id: match a.category {
+                    Category::Noise => -1,
+                    _ => cluster_id as i64,
+                },
+                point_index: a.index,
+                label: a.label,
+                category: a.category.clone(),
+            })?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads clusters written by [`write_clusters_csv`] from `reader`.
+///
+/// Clusters are ordered by `cluster_id`, followed by the noise cluster (if any).
+pub fn read_clusters_csv<R: Read, S: DeserializeOwned>(
+    reader: R,
+) -> Result<Vec<Cluster<S>>, CsvError> {
+    let mut clusters: Vec<Cluster<S>> = Vec::new();
+    let mut noise_cluster = Vec::new();
+    for row in csv::Reader::from_reader(reader).deserialize() {
+        let row: Row<S> = row?;
+        let assignment = Assignment {
+            index: row.point_index,
+            cluster_id: usize::try_from(row.cluster_id).ok(),
+            label: row.label,
+            category: row.category,
+        };
+        if row.cluster_id < 0 {
+            noise_cluster.push(assignment);
+        } else {
+            let cluster_id = row.cluster_id as usize;
+            if clusters.len() <= cluster_id {
+                clusters.resize_with(cluster_id + 1, Vec::new);
+            }
+            clusters[cluster_id].push(assignment);
+        }
+    }
+    if !noise_cluster.is_empty() {
+        clusters.push(noise_cluster);
+    }
+    Ok(clusters)
+}
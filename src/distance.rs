@@ -0,0 +1,92 @@
+//! Distance functions between coordinate vectors.
+use num_traits::Float;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A trait to compute the distance of two coordinate vectors of equal length.
+///
+/// This trait is sealed, i.e., it is implemented by [`Euclidean`], [`Manhattan`], [`Chebyshev`],
+/// and [`Cosine`] only.
+pub trait DistanceFn: sealed::Sealed {
+    /// Whether [`distance`](Self::distance) is the square root of
+    /// [`distance_squared`](Self::distance_squared).
+    const SQRT_OF_SQUARED: bool = false;
+
+    /// Returns the distance between `a` and `b`.
+    fn distance<S: Float>(a: &[S], b: &[S]) -> S;
+
+    /// Returns the squared distance between `a` and `b`.
+    fn distance_squared<S: Float>(a: &[S], b: &[S]) -> S {
+        Self::distance(a, b).powi(2)
+    }
+}
+
+/// The Euclidean (L2) distance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Euclidean;
+
+impl sealed::Sealed for Euclidean {}
+
+impl DistanceFn for Euclidean {
+    const SQRT_OF_SQUARED: bool = true;
+
+    fn distance<S: Float>(a: &[S], b: &[S]) -> S {
+        Self::distance_squared(a, b).sqrt()
+    }
+
+    fn distance_squared<S: Float>(a: &[S], b: &[S]) -> S {
+        a.iter()
+            .zip(b)
+            .fold(S::zero(), |sum, (&a, &b)| sum + (b - a).powi(2))
+    }
+}
+
+/// The Manhattan (L1) distance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Manhattan;
+
+impl sealed::Sealed for Manhattan {}
+
+impl DistanceFn for Manhattan {
+    fn distance<S: Float>(a: &[S], b: &[S]) -> S {
+        a.iter()
+            .zip(b)
+            .fold(S::zero(), |sum, (&a, &b)| sum + (b - a).abs())
+    }
+}
+
+/// The Chebyshev (L∞) distance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Chebyshev;
+
+impl sealed::Sealed for Chebyshev {}
+
+impl DistanceFn for Chebyshev {
+    fn distance<S: Float>(a: &[S], b: &[S]) -> S {
+        a.iter()
+            .zip(b)
+            .fold(S::zero(), |max, (&a, &b)| max.max((b - a).abs()))
+    }
+}
+
+/// The cosine distance, i.e., one minus the cosine of the angle between both vectors, which is
+/// `1` if either vector has zero norm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Cosine;
+
+impl sealed::Sealed for Cosine {}
+
+impl DistanceFn for Cosine {
+    fn distance<S: Float>(a: &[S], b: &[S]) -> S {
+        let (dot, norm_a, norm_b) = a.iter().zip(b).fold(
+            (S::zero(), S::zero(), S::zero()),
+            |(dot, norm_a, norm_b), (&a, &b)| (dot + a * b, norm_a + a * a, norm_b + b * b),
+        );
+        if norm_a == S::zero() || norm_b == S::zero() {
+            return S::one();
+        }
+        S::one() - dot / (norm_a.sqrt() * norm_b.sqrt())
+    }
+}
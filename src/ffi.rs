@@ -0,0 +1,135 @@
+//! C bindings.
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::mem;
+use core::ptr;
+use core::slice;
+
+use crate::{Category, FuzzyDBSCAN, NDPoint};
+
+/// An assignment of a point to a cluster for C.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FfiAssignment {
+    /// The point index.
+    pub index: usize,
+    /// The cluster identifier, or `-1` for noise.
+    pub cluster_id: i64,
+    /// The soft label between `0.0` and `1.0`.
+    pub label: f32,
+    /// The category, which is `0` (core), `1` (border), or `2` (noise).
+    pub category: u8,
+}
+
+/// Creates an instance of the FuzzyDBSCAN algorithm with the given parameters and the defaults
+/// otherwise, or returns null if the parameters are invalid.
+///
+/// The instance must be freed by [`fuzzy_dbscan_free`].
+#[no_mangle]
+pub extern "C" fn fuzzy_dbscan_create(
+    eps_min: f32,
+    eps_max: f32,
+    pts_min: f32,
+    pts_max: f32,
+) -> *mut FuzzyDBSCAN<f32> {
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min,
+        eps_max,
+        pts_min,
+        pts_max,
+        ..Default::default()
+    };
+    match fuzzy_dbscan.validate() {
+        Ok(()) => Box::into_raw(Box::new(fuzzy_dbscan)),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Frees an instance created by [`fuzzy_dbscan_create`], which may be null.
+///
+/// # Safety
+///
+/// `fuzzy_dbscan` must be null or returned by [`fuzzy_dbscan_create`], and must not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzy_dbscan_free(fuzzy_dbscan: *mut FuzzyDBSCAN<f32>) {
+    if !fuzzy_dbscan.is_null() {
+        drop(Box::from_raw(fuzzy_dbscan));
+    }
+}
+
+/// Clusters `n_points` points whose `dimensions` coordinates are stored consecutively in
+/// `points`, e.g., `[x0, y0, x1, y1, ...]` for 2D points.
+///
+/// Returns the assignments of all clusters, followed by the noise points, and writes their
+/// number to `n_assignments`. Returns null and writes zero if clustering fails or the number of
+/// coordinates is too large for a slice. The assignments must be freed by
+/// [`fuzzy_dbscan_free_result`].
+///
+/// # Safety
+///
+/// `fuzzy_dbscan` must be returned by [`fuzzy_dbscan_create`], `points` must point to
+/// `n_points * dimensions` floats, and `n_assignments` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzy_dbscan_cluster(
+    fuzzy_dbscan: *const FuzzyDBSCAN<f32>,
+    points: *const f32,
+    n_points: usize,
+    dimensions: usize,
+    n_assignments: *mut usize,
+) -> *mut FfiAssignment {
+    *n_assignments = 0;
+    if fuzzy_dbscan.is_null() || points.is_null() || dimensions == 0 {
+        return ptr::null_mut();
+    }
+    let n_coordinates = match n_points.checked_mul(dimensions) {
+        Some(n_coordinates) if n_coordinates <= isize::MAX as usize / mem::size_of::<f32>() => {
+            n_coordinates
+        }
+        _ => return ptr::null_mut(),
+    };
+    let coordinates = slice::from_raw_parts(points, n_coordinates);
+    let points: Vec<NDPoint<f32>> = coordinates
+        .chunks_exact(dimensions)
+        .map(|point| NDPoint::new(point.to_vec()))
+        .collect();
+    let clusters = match (*fuzzy_dbscan).cluster(&points) {
+        Ok(clusters) => clusters,
+        Err(_) => return ptr::null_mut(),
+    };
+    let assignments: Box<[FfiAssignment]> = clusters
+        .iter()
+        .flatten()
+        .map(|a| FfiAssignment {
+            index: a.index,
+            cluster_id: a.cluster_id.map_or(-1, |cluster_id| cluster_id as i64),
+            label: a.label,
+            category: match a.category {
+                Category::Core => 0,
+                Category::Border => 1,
+                Category::Noise => 2,
+            },
+        })
+        .collect();
+    *n_assignments = assignments.len();
+    Box::into_raw(assignments) as *mut FfiAssignment
+}
+
+/// Frees `n_assignments` assignments returned by [`fuzzy_dbscan_cluster`], which may be null.
+///
+/// # Safety
+///
+/// `assignments` must be null or returned by [`fuzzy_dbscan_cluster`] along with
+/// `n_assignments`, and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzy_dbscan_free_result(
+    assignments: *mut FfiAssignment,
+    n_assignments: usize,
+) {
+    if !assignments.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+            assignments,
+            n_assignments,
+        )));
+    }
+}
@@ -0,0 +1,138 @@
+//! Point-indexed clustering results.
+use alloc::vec;
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::{Category, Cluster, FuzzyDBSCAN, FuzzyDBSCANError, MetricSpace};
+
+/// The assignment of a point to its most likely cluster.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlatAssignment<S = f64> {
+    /// The cluster index, or `None` for noise.
+    pub cluster_id: Option<usize>,
+    /// A (soft) label between `0.0` and `1.0`.
+    pub label: S,
+    /// A high-level category.
+    pub category: Category,
+}
+
+/// The memberships of a point in all clusters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultiAssignment<S = f64> {
+    /// The point index.
+    pub index: usize,
+    /// The cluster indices and labels, empty for noise.
+    pub memberships: Vec<(usize, S)>,
+}
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Clusters a list of `points` and returns one assignment per point, in the order of `points`.
+    ///
+    /// Points that belong to multiple clusters keep the membership with the highest label (the
+    /// first one on ties). Points are only reported as noise if they belong to no cluster.
+    pub fn cluster_flat<P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+    ) -> Result<Vec<FlatAssignment<S>>, FuzzyDBSCANError> {
+        let clusters = self.cluster(points)?;
+        let mut flat: Vec<Option<FlatAssignment<S>>> = vec![None; points.len()];
+        for (cluster_id, cluster) in clusters.iter().enumerate() {
+            for assignment in cluster {
+                let cluster_id = match assignment.category {
+                    Category::Noise => None,
+                    _ => Some(cluster_id),
+                };
+                let replace = match &flat[assignment.index] {
+                    None => true,
+                    Some(current) => {
+                        current.cluster_id.is_none()
+                            || (cluster_id.is_some() && assignment.label > current.label)
+                    }
+                };
+                if replace {
+                    flat[assignment.index] = Some(FlatAssignment {
+                        cluster_id,
+                        label: assignment.label,
+                        category: assignment.category.clone(),
+                    });
+                }
+            }
+        }
+        Ok(flat
+            .into_iter()
+            .map(|assignment| assignment.expect("every point is assigned"))
+            .collect())
+    }
+
+    /// Clusters a list of `points` and returns all memberships per point, in the order of `points`.
+    ///
+    /// Unlike [`cluster_flat`](FuzzyDBSCAN::cluster_flat), border points keep their membership
+    /// in every cluster they belong to with a label greater than zero.
+    pub fn cluster_multi<P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+    ) -> Result<Vec<MultiAssignment<S>>, FuzzyDBSCANError> {
+        let clusters = self.cluster(points)?;
+        let mut multi: Vec<MultiAssignment<S>> = (0..points.len())
+            .map(|index| MultiAssignment {
+                index,
+                memberships: Vec::new(),
+            })
+            .collect();
+        for (cluster_id, cluster) in clusters.iter().enumerate() {
+            for assignment in cluster {
+                if assignment.category == Category::Noise || assignment.label <= S::zero() {
+                    continue;
+                }
+                let memberships = &mut multi[assignment.index].memberships;
+                match memberships.iter_mut().find(|(id, _)| *id == cluster_id) {
+                    Some((_, label)) => *label = label.max(assignment.label),
+                    None => memberships.push((cluster_id, assignment.label)),
+                }
+            }
+        }
+        Ok(multi)
+    }
+}
+
+/// Converts `clusters` of `n_points` points into a partition matrix, where the entry at `[i][c]`
+/// is the label of point `i` in cluster `c`.
+///
+/// Noise has a label of `0.0` in all clusters. Note that rows may sum up to more than `1.0` if
+/// border points belong to multiple clusters.
+pub fn cluster_to_partition_matrix<S: Float>(
+    n_points: usize,
+    clusters: &[Cluster<S>],
+) -> Vec<Vec<S>> {
+    let mut matrix = vec![vec![S::zero(); clusters.len()]; n_points];
+    for (cluster_id, cluster) in clusters.iter().enumerate() {
+        for assignment in cluster {
+            if assignment.category != Category::Noise {
+                let label = &mut matrix[assignment.index][cluster_id];
+                *label = label.max(assignment.label);
+            }
+        }
+    }
+    matrix
+}
+
+/// Returns, for each of `n_points` points, the index of the cluster with the highest label (the
+/// lower index on ties), or `None` if the point is noise in all clusters.
+pub fn defuzzify<S: Float>(n_points: usize, clusters: &[Cluster<S>]) -> Vec<Option<usize>> {
+    let mut assignments: Vec<Option<(usize, S)>> = vec![None; n_points];
+    for (cluster_id, cluster) in clusters.iter().enumerate() {
+        for assignment in cluster {
+            if assignment.category == Category::Noise {
+                continue;
+            }
+            match assignments[assignment.index] {
+                Some((_, label)) if label >= assignment.label => {}
+                _ => assignments[assignment.index] = Some((cluster_id, assignment.label)),
+            }
+        }
+    }
+    assignments
+        .into_iter()
+        .map(|assignment| assignment.map(|(cluster_id, _)| cluster_id))
+        .collect()
+}
@@ -0,0 +1,36 @@
+//! Geographic points.
+use num_traits::Float;
+
+use crate::MetricSpace;
+
+/// The mean radius of the Earth in kilometers.
+const EARTH_RADIUS: f64 = 6371.0088;
+
+/// A point on the surface of the Earth, whose distance is the great-circle distance in
+/// kilometers as computed by the haversine formula.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct GeoPoint {
+    /// The latitude in degrees.
+    pub lat: f64,
+    /// The longitude in degrees.
+    pub lon: f64,
+}
+
+impl GeoPoint {
+    /// Creates a new point at latitude `lat` and longitude `lon` in degrees.
+    pub fn new(lat: f64, lon: f64) -> Self {
+        GeoPoint { lat, lon }
+    }
+}
+
+impl MetricSpace for GeoPoint {
+    fn distance(&self, other: &Self) -> f64 {
+        let (lat_a, lat_b) = (self.lat.to_radians(), other.lat.to_radians());
+        let delta_lat = lat_b - lat_a;
+        let delta_lon = (other.lon - self.lon).to_radians();
+        // Call the trait methods explicitly, as the inherent ones require std.
+        let h = Float::sin(delta_lat / 2.0).powi(2)
+            + Float::cos(lat_a) * Float::cos(lat_b) * Float::sin(delta_lon / 2.0).powi(2);
+        2.0 * EARTH_RADIUS * Float::asin(Float::sqrt(h).min(1.0))
+    }
+}
@@ -0,0 +1,78 @@
+//! Graphs of fuzzy neighborhoods.
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::index::{BruteForce, SpatialIndex};
+use crate::{Category, Context, FuzzyDBSCAN, FuzzyDBSCANError, MetricSpace};
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Clusters a list of `points` and returns the edges between core points of the same
+    /// cluster that are neighbors or share a common neighbor, thus the connected components of the
+    /// graph are the clusters (without their border points).
+    ///
+    /// Each edge `(a, b, mu)` is listed once with `a < b`, sorted by `a` and then `b`, and is
+    /// weighted by the minimum distance membership `mu` along the path between its points, or the
+    /// maximum of these if there are several paths. Cores exactly `eps_max` apart are connected
+    /// with a weight of zero.
+    pub fn core_connectivity_graph<P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+    ) -> Result<Vec<(usize, usize, S)>, FuzzyDBSCANError> {
+        let clusters = self.cluster(points)?;
+        let mut core_cluster_ids = vec![None; points.len()];
+        for (cluster_index, cluster) in clusters.real_clusters().iter().enumerate() {
+            for assignment in cluster.iter().filter(|a| a.category == Category::Core) {
+                core_cluster_ids[assignment.index] = Some(cluster_index);
+            }
+        }
+        let neighbor_graph = self.neighbor_graph(points);
+        let mut edges = Vec::new();
+        for a in 0..points.len() {
+            let Some(cluster_index) = core_cluster_ids[a] else {
+                continue;
+            };
+            let is_connected = |b: usize| b > a && core_cluster_ids[b] == Some(cluster_index);
+            let mut weights: BTreeMap<usize, S> = BTreeMap::new();
+            let mut connect = |b: usize, mu: S| {
+                let weight = weights.entry(b).or_insert(mu);
+                *weight = weight.max(mu);
+            };
+            for &(c, mu_ac) in &neighbor_graph[a] {
+                if is_connected(c) {
+                    connect(c, mu_ac);
+                }
+                for &(b, mu_cb) in &neighbor_graph[c] {
+                    if is_connected(b) {
+                        connect(b, mu_ac.min(mu_cb));
+                    }
+                }
+            }
+            edges.extend(weights.into_iter().map(|(b, mu)| (a, b, mu)));
+        }
+        Ok(edges)
+    }
+
+    /// Returns the fuzzy neighborhood graph of a list of `points` before clusters are assigned,
+    /// i.e., the `(neighbor_index, mu_distance)` of every neighbor within `eps_max` for each point.
+    ///
+    /// The neighbors are found by the same region query as when clustering, thus each edge is
+    /// listed for both of its points and neighbors are sorted by their index.
+    pub fn neighbor_graph<P: MetricSpace<S>>(&self, points: &[P]) -> Vec<Vec<(usize, S)>> {
+        let context = Context {
+            points,
+            weights: None,
+            index: BruteForce::build(points, self.eps_max),
+            neighborhoods: Vec::new(),
+        };
+        (0..points.len())
+            .map(|point_index| {
+                self.fuzzy_region_query(point_index, &context)
+                    .into_iter()
+                    .map(|(neighbor_index, _, mu_distance)| (neighbor_index, mu_distance))
+                    .collect()
+            })
+            .collect()
+    }
+}
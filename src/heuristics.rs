@@ -0,0 +1,136 @@
+//! Heuristics to choose clustering parameters.
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use num_traits::Float;
+
+use crate::{defuzzify, metrics, Category, FuzzyDBSCAN, FuzzyDBSCANError, MetricSpace};
+
+/// Returns the distance of every point to its `k`-th nearest neighbor, sorted ascending.
+///
+/// Plotting these distances yields the k-distance graph, whose "elbow" is a good choice for
+/// `eps` when `pts` is set to `k`, see [`elbow_index`]. NaN distances are sorted last.
+///
+/// Returns an error if `k` is zero or not less than the number of points.
+pub fn k_distance_graph<S: Float, P: MetricSpace<S>>(
+    points: &[P],
+    k: usize,
+) -> Result<Vec<S>, FuzzyDBSCANError> {
+    if k == 0 || k >= points.len() {
+        return Err(FuzzyDBSCANError::InvalidNeighborCount);
+    }
+    let mut k_distances: Vec<S> = (0..points.len())
+        .map(|point_index| {
+            let mut distances: Vec<S> = points
+                .iter()
+                .enumerate()
+                .filter(|&(neighbor_index, _)| neighbor_index != point_index)
+                .map(|(_, neighbor_point)| neighbor_point.distance(&points[point_index]))
+                .collect();
+            let (_, k_distance, _) = distances.select_nth_unstable_by(k - 1, nan_last);
+            *k_distance
+        })
+        .collect();
+    k_distances.sort_unstable_by(nan_last);
+    Ok(k_distances)
+}
+
+/// Compares two scalars in ascending order, where NaN is greater than any other value.
+fn nan_last<S: Float>(a: &S, b: &S) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.partial_cmp(b).unwrap(),
+    }
+}
+
+/// Returns the index of the elbow of sorted `distances`, i.e., the point of maximum curvature
+/// as approximated by the largest second difference.
+///
+/// Returns 0 if there are less than three distances.
+pub fn elbow_index<S: Float>(distances: &[S]) -> usize {
+    distances
+        .windows(3)
+        .map(|w| w[0] - w[1] - w[1] + w[2])
+        .enumerate()
+        .fold((0, S::neg_infinity()), |best, (i, curvature)| {
+            if curvature > best.1 {
+                (i + 1, curvature)
+            } else {
+                best
+            }
+        })
+        .0
+}
+
+/// The outcome of clustering with one parameter combination of a [`parameter_sweep`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SweepResult<S = f64> {
+    /// The minimum fuzzy local neighborhood radius.
+    pub eps_min: S,
+    /// The maximum fuzzy local neighborhood radius.
+    pub eps_max: S,
+    /// The minimum fuzzy neighborhood density (number of points).
+    pub pts_min: S,
+    /// The maximum fuzzy neighborhood density (number of points).
+    pub pts_max: S,
+    /// The number of clusters, excluding noise.
+    pub cluster_count: usize,
+    /// The mean silhouette coefficient, see [`metrics::silhouette_score`].
+    pub silhouette: S,
+    /// The fraction of points that belong to no cluster.
+    pub noise_fraction: S,
+}
+
+/// Clusters `points` with every combination of the given parameter values, in lexicographic
+/// order of `(eps_min, eps_max, pts_min, pts_max)`.
+///
+/// Combinations with invalid parameters, such as `eps_min > eps_max`, are skipped, all other
+/// parameters are left at their defaults. Returns an empty list if there are no points.
+pub fn parameter_sweep<S: Float + Send + Sync, P: MetricSpace<S>>(
+    points: &[P],
+    eps_min_values: &[S],
+    eps_max_values: &[S],
+    pts_min_values: &[S],
+    pts_max_values: &[S],
+) -> Vec<SweepResult<S>> {
+    let mut results = Vec::new();
+    for &eps_min in eps_min_values {
+        for &eps_max in eps_max_values {
+            for &pts_min in pts_min_values {
+                for &pts_max in pts_max_values {
+                    let fuzzy_dbscan = match FuzzyDBSCAN::builder()
+                        .eps_min(eps_min)
+                        .eps_max(eps_max)
+                        .pts_min(pts_min)
+                        .pts_max(pts_max)
+                        .build()
+                    {
+                        Ok(fuzzy_dbscan) => fuzzy_dbscan,
+                        Err(_) => continue,
+                    };
+                    let clusters = match fuzzy_dbscan.cluster(points) {
+                        Ok(clusters) => clusters,
+                        Err(_) => continue,
+                    };
+                    let assignments = defuzzify(points.len(), &clusters);
+                    let noise_count = assignments.iter().filter(|a| a.is_none()).count();
+                    results.push(SweepResult {
+                        eps_min,
+                        eps_max,
+                        pts_min,
+                        pts_max,
+                        cluster_count: clusters
+                            .iter()
+                            .filter(|cluster| cluster.iter().any(|a| a.category != Category::Noise))
+                            .count(),
+                        silhouette: metrics::silhouette_score(points, &clusters),
+                        noise_fraction: S::from(noise_count).unwrap()
+                            / S::from(points.len()).unwrap(),
+                    });
+                }
+            }
+        }
+    }
+    results
+}
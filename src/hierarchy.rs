@@ -0,0 +1,79 @@
+//! Hierarchies of clusterings with decreasing densities.
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::{Category, Cluster, FuzzyDBSCAN, FuzzyDBSCANError, MetricSpace};
+
+/// An edge of a [cluster hierarchy](ClusterHierarchy) from a cluster to the cluster of the next
+/// coarser level that it merges into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HierarchyEdge {
+    /// The level of the child cluster, the parent cluster is at `level + 1`.
+    pub level: usize,
+    /// The identifier of the child cluster.
+    pub child: usize,
+    /// The identifier of the parent cluster.
+    pub parent: usize,
+}
+
+/// The clusterings of a list of points for decreasing values of `pts_min`, connected by the
+/// edges of a dendrogram.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterHierarchy<S = f64> {
+    /// The value of `pts_min` of each level, in descending order.
+    pub pts_min_steps: Vec<S>,
+    /// The clusters of each level, excluding noise.
+    pub levels: Vec<Vec<Cluster<S>>>,
+    /// The edges from every cluster, except for those of the coarsest level, to its parent.
+    pub edges: Vec<HierarchyEdge>,
+}
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Clusters a list of `points` for each value of `pts_min_steps` in descending order and
+    /// tracks which clusters of a level merge into which cluster of the next coarser level.
+    ///
+    /// Neighborhoods are [computed once](Self::precompute_neighbors) and `pts_max` is raised to
+    /// the value of `pts_min` where necessary. Since decreasing `pts_min` only adds core points,
+    /// all core points of a cluster stay within the same cluster of the next level.
+    pub fn hierarchical_merge<P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+        pts_min_steps: &[S],
+    ) -> Result<ClusterHierarchy<S>, FuzzyDBSCANError> {
+        let neighbors = self.precompute_neighbors(points)?;
+        let mut pts_min_steps = pts_min_steps.to_vec();
+        pts_min_steps.sort_by(|a, b| b.partial_cmp(a).unwrap_or(core::cmp::Ordering::Equal));
+        let mut levels = Vec::with_capacity(pts_min_steps.len());
+        for &pts_min in &pts_min_steps {
+            let mut clusters = self.recluster(&neighbors, pts_min, pts_min.max(self.pts_max))?;
+            clusters.retain(|cluster| cluster.iter().any(|a| a.category != Category::Noise));
+            levels.push(clusters);
+        }
+        let mut edges = Vec::new();
+        for (level, pair) in levels.windows(2).enumerate() {
+            let (children, parents) = (&pair[0], &pair[1]);
+            for (child, cluster) in children.iter().enumerate() {
+                let core = cluster.iter().find(|a| a.category == Category::Core);
+                let parent = core.and_then(|core| {
+                    parents.iter().position(|parent| {
+                        parent
+                            .iter()
+                            .any(|a| a.index == core.index && a.category == Category::Core)
+                    })
+                });
+                if let Some(parent) = parent {
+                    edges.push(HierarchyEdge {
+                        level,
+                        child,
+                        parent,
+                    });
+                }
+            }
+        }
+        Ok(ClusterHierarchy {
+            pts_min_steps,
+            levels,
+            edges,
+        })
+    }
+}
@@ -0,0 +1,310 @@
+//! Spatial indices to speed up neighborhood queries.
+//!
+//! By default, [`FuzzyDBSCAN`](crate::FuzzyDBSCAN) uses the [brute-force](BruteForce) index, which
+//! compares every pair of points and thus takes `O(n²)` time in total. Enable the `kiddo` feature
+//! to use a KD-tree (`KdTree`) instead, or use the [bounding box](BoundingBoxIndex) index to
+//! skip most distance computations without dependencies, or the [grid](GridIndex) index for
+//! uniformly distributed 2D points. Enable the `rayon` feature to run brute-force queries in
+//! parallel. Enable the `deterministic` feature to keep results reproducible with `rayon`.
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::MetricSpace;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// A trait to find the neighborhood of points.
+pub trait SpatialIndex<P, S = f64>: Sized + crate::sync::MaybeSync {
+    /// Builds an index over `points` that finds all neighbors within radius `eps`.
+    fn build(points: &[P], eps: S) -> Self;
+
+    /// Returns the sorted indices of all points within `eps` of the point at `point_index`,
+    /// excluding `point_index` itself.
+    fn region_query(&self, points: &[P], point_index: usize) -> Vec<usize>;
+
+    /// Returns the sorted indices of all points within `eps` of the point at `point_index`,
+    /// excluding `point_index` itself, together with their distances.
+    ///
+    /// Override this method if distances are known anyway, to avoid computing them twice.
+    fn region_query_with_distances(&self, points: &[P], point_index: usize) -> Vec<(usize, S)>
+    where
+        P: MetricSpace<S>,
+        S: Float,
+    {
+        self.region_query(points, point_index)
+            .into_iter()
+            .map(|neighbor_index| {
+                let distance = points[point_index].distance(&points[neighbor_index]);
+                (neighbor_index, distance)
+            })
+            .collect()
+    }
+}
+
+/// An index that compares the query point with every other point.
+pub struct BruteForce<S = f64> {
+    eps: S,
+}
+
+impl<P: MetricSpace<S>, S: Float + Send + Sync> SpatialIndex<P, S> for BruteForce<S> {
+    fn build(_points: &[P], eps: S) -> Self {
+        BruteForce { eps }
+    }
+
+    fn region_query(&self, points: &[P], point_index: usize) -> Vec<usize> {
+        self.region_query_with_distances(points, point_index)
+            .into_iter()
+            .map(|(neighbor_index, _)| neighbor_index)
+            .collect()
+    }
+
+    fn region_query_with_distances(&self, points: &[P], point_index: usize) -> Vec<(usize, S)> {
+        #[cfg(not(feature = "rayon"))]
+        let points_iter = points.iter();
+        #[cfg(feature = "rayon")]
+        let points_iter = points.par_iter();
+        let candidates = points_iter
+            .enumerate()
+            .filter(|(neighbor_index, _)| *neighbor_index != point_index);
+        if P::SQRT_OF_SQUARED {
+            candidates
+                .map(|(neighbor_index, neighbor_point)| {
+                    (
+                        neighbor_index,
+                        neighbor_point.distance_squared(&points[point_index]),
+                    )
+                })
+                .filter(|(_, distance_squared)| *distance_squared <= self.eps * self.eps)
+                .map(|(neighbor_index, distance_squared)| (neighbor_index, distance_squared.sqrt()))
+                .collect() //TODO: would be neat to prevent this allocation.
+        } else {
+            candidates
+                .map(|(neighbor_index, neighbor_point)| {
+                    (neighbor_index, points[point_index].distance(neighbor_point))
+                })
+                .filter(|(_, distance)| *distance <= self.eps)
+                .collect()
+        }
+    }
+}
+
+/// An index that sweeps over the points sorted by their first coordinate and rejects candidates
+/// whose coordinates differ by more than `eps` on any axis, i.e., lie outside the bounding box of
+/// the query, before computing their distance.
+///
+/// Note that the [distance](MetricSpace::distance) of the points must be at least the difference
+/// of their [coordinates](crate::Coordinates) on every axis, e.g., any Minkowski distance.
+pub struct BoundingBoxIndex<const K: usize, S = f64> {
+    coordinates: Vec<[S; K]>,
+    /// The point indices sorted by their first coordinate.
+    sorted: Vec<usize>,
+    eps: S,
+}
+
+impl<P, S, const K: usize> SpatialIndex<P, S> for BoundingBoxIndex<K, S>
+where
+    P: MetricSpace<S> + crate::Coordinates<K, S>,
+    S: Float + Send + Sync,
+{
+    fn build(points: &[P], eps: S) -> Self {
+        let coordinates: Vec<[S; K]> = points.iter().map(|point| point.coordinates()).collect();
+        let mut sorted: Vec<usize> = (0..points.len()).collect();
+        if K > 0 {
+            sorted.sort_by(|&a, &b| {
+                let (a, b) = (coordinates[a][0], coordinates[b][0]);
+                a.partial_cmp(&b).unwrap_or(core::cmp::Ordering::Equal)
+            });
+        }
+        BoundingBoxIndex {
+            coordinates,
+            sorted,
+            eps,
+        }
+    }
+
+    fn region_query(&self, points: &[P], point_index: usize) -> Vec<usize> {
+        self.region_query_with_distances(points, point_index)
+            .into_iter()
+            .map(|(neighbor_index, _)| neighbor_index)
+            .collect()
+    }
+
+    fn region_query_with_distances(&self, points: &[P], point_index: usize) -> Vec<(usize, S)> {
+        // Rounding may make distances slightly smaller than coordinate differences, thus enlarge
+        // the box slightly and filter the candidates using the exact distance.
+        let half_width = self.eps * (S::one() + S::from(4.0).unwrap() * S::epsilon());
+        let query = &self.coordinates[point_index];
+        let candidates = match query.first() {
+            Some(&x) => {
+                let start = self
+                    .sorted
+                    .partition_point(|&index| self.coordinates[index][0] < x - half_width);
+                let end = self
+                    .sorted
+                    .partition_point(|&index| self.coordinates[index][0] <= x + half_width);
+                &self.sorted[start.min(end)..end]
+            }
+            None => &self.sorted[..],
+        };
+        let mut neighbors: Vec<(usize, S)> = candidates
+            .iter()
+            .copied()
+            .filter(|&neighbor_index| neighbor_index != point_index)
+            .filter(|&neighbor_index| {
+                let coordinates = &self.coordinates[neighbor_index];
+                query
+                    .iter()
+                    .zip(coordinates.iter())
+                    .all(|(&a, &b)| (a - b).abs() <= half_width)
+            })
+            .map(|neighbor_index| {
+                let distance = points[neighbor_index].distance(&points[point_index]);
+                (neighbor_index, distance)
+            })
+            .filter(|&(_, distance)| distance <= self.eps)
+            .collect();
+        neighbors.sort_unstable_by_key(|&(neighbor_index, _)| neighbor_index);
+        neighbors
+    }
+}
+
+/// An index that sorts 2D points into square cells of width `eps`, such that the neighbors of a
+/// point lie in the surrounding cells only, which suits uniformly distributed points.
+///
+/// Note that the [distance](MetricSpace::distance) of the points must be at least the difference
+/// of their [coordinates](crate::Coordinates) on every axis, e.g., any Minkowski distance.
+pub struct GridIndex<S = f64> {
+    cells: BTreeMap<(i64, i64), Vec<usize>>,
+    coordinates: Vec<[S; 2]>,
+    cell_size: S,
+    eps: S,
+}
+
+impl<S: Float> GridIndex<S> {
+    /// Returns the cell of `coordinates`, saturating for coordinates out of range.
+    fn cell(&self, coordinates: [S; 2]) -> (i64, i64) {
+        let cell = |coordinate: S| {
+            let cell = (coordinate / self.cell_size).floor();
+            cell.to_i64()
+                .unwrap_or(if cell > S::zero() { i64::MAX } else { i64::MIN })
+        };
+        (cell(coordinates[0]), cell(coordinates[1]))
+    }
+}
+
+impl<P, S> SpatialIndex<P, S> for GridIndex<S>
+where
+    P: MetricSpace<S> + crate::Coordinates<2, S>,
+    S: Float + Send + Sync,
+{
+    fn build(points: &[P], eps: S) -> Self {
+        let mut index = GridIndex {
+            cells: BTreeMap::new(),
+            coordinates: points.iter().map(|point| point.coordinates()).collect(),
+            cell_size: if eps > S::zero() { eps } else { S::one() },
+            eps,
+        };
+        for point_index in 0..points.len() {
+            let cell = index.cell(index.coordinates[point_index]);
+            index.cells.entry(cell).or_default().push(point_index);
+        }
+        index
+    }
+
+    fn region_query(&self, points: &[P], point_index: usize) -> Vec<usize> {
+        self.region_query_with_distances(points, point_index)
+            .into_iter()
+            .map(|(neighbor_index, _)| neighbor_index)
+            .collect()
+    }
+
+    fn region_query_with_distances(&self, points: &[P], point_index: usize) -> Vec<(usize, S)> {
+        // Rounding is monotonic, thus the cells of the rounded bounds of the query enclose the
+        // cells of all neighbors, which are usually the 3x3 cells around the query.
+        let [x, y] = self.coordinates[point_index];
+        let (min_x, min_y) = self.cell([x - self.eps, y - self.eps]);
+        let (max_x, max_y) = self.cell([x + self.eps, y + self.eps]);
+        let mut neighbors = Vec::new();
+        for cell_x in min_x..=max_x {
+            for (_, cell) in self.cells.range((cell_x, min_y)..=(cell_x, max_y)) {
+                for &neighbor_index in cell {
+                    if neighbor_index == point_index {
+                        continue;
+                    }
+                    let distance = points[neighbor_index].distance(&points[point_index]);
+                    if distance <= self.eps {
+                        neighbors.push((neighbor_index, distance));
+                    }
+                }
+            }
+        }
+        neighbors.sort_unstable_by_key(|&(neighbor_index, _)| neighbor_index);
+        neighbors
+    }
+}
+
+/// An index backed by a KD-tree, which answers queries in `O(log n)` average time.
+///
+/// Note that the [distance](MetricSpace::distance) of the points must be the Euclidean distance
+/// between their [coordinates](crate::Coordinates). The tree stores coordinates as `f64`, which
+/// only finds candidates, whereas neighbors are found using the distance of scalar type `S`.
+#[cfg(feature = "kiddo")]
+pub struct KdTree<const K: usize, S = f64> {
+    tree: kiddo::ImmutableKdTree<f64, K>,
+    eps: S,
+}
+
+#[cfg(feature = "kiddo")]
+impl<S, P, const K: usize> SpatialIndex<P, S> for KdTree<K, S>
+where
+    S: Float + crate::sync::MaybeSync,
+    P: MetricSpace<S> + crate::Coordinates<K, S>,
+{
+    fn build(points: &[P], eps: S) -> Self {
+        let coordinates: Vec<[f64; K]> = points
+            .iter()
+            .map(|point| to_f64_coordinates(point.coordinates()))
+            .collect();
+        KdTree {
+            tree: kiddo::ImmutableKdTree::new_from_slice(&coordinates),
+            eps,
+        }
+    }
+
+    fn region_query(&self, points: &[P], point_index: usize) -> Vec<usize> {
+        self.region_query_with_distances(points, point_index)
+            .into_iter()
+            .map(|(neighbor_index, _)| neighbor_index)
+            .collect()
+    }
+
+    fn region_query_with_distances(&self, points: &[P], point_index: usize) -> Vec<(usize, S)> {
+        // The tree excludes points exactly at the radius, thus query a slightly larger radius
+        // (by the precision of S) and filter the candidates using the exact distance.
+        let eps = self.eps.to_f64().unwrap();
+        let radius = eps * eps * (1.0 + 4.0 * S::epsilon().to_f64().unwrap());
+        let query = to_f64_coordinates(points[point_index].coordinates());
+        let mut neighbors = self
+            .tree
+            .within_unsorted::<kiddo::SquaredEuclidean>(&query, radius)
+            .into_iter()
+            .map(|neighbor| neighbor.item as usize)
+            .filter(|&neighbor_index| neighbor_index != point_index)
+            .map(|neighbor_index| {
+                let distance = points[neighbor_index].distance(&points[point_index]);
+                (neighbor_index, distance)
+            })
+            .filter(|&(_, distance)| distance <= self.eps)
+            .collect::<Vec<_>>();
+        neighbors.sort_unstable_by_key(|&(neighbor_index, _)| neighbor_index);
+        neighbors
+    }
+}
+
+/// Converts `coordinates` of scalar type `S` to `f64`.
+#[cfg(feature = "kiddo")]
+fn to_f64_coordinates<S: Float, const K: usize>(coordinates: [S; K]) -> [f64; K] {
+    coordinates.map(|coordinate| coordinate.to_f64().unwrap())
+}
@@ -0,0 +1,84 @@
+//! Lazy cluster expansion.
+use alloc::vec;
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::index::BruteForce;
+use crate::{
+    add_to_noise, Cluster, Context, Expansion, FuzzyDBSCAN, FuzzyDBSCANError, MetricSpace,
+};
+
+/// An iterator over the clusters of a list of points, which expands the next cluster only when
+/// it is requested.
+///
+/// Clusters are yielded in the order of discovery, followed by the noise cluster (if any), and
+/// are identical to those returned by [`FuzzyDBSCAN::cluster`].
+pub struct ClusterIter<'a, P, S = f64> {
+    fuzzy_dbscan: &'a FuzzyDBSCAN<S>,
+    context: Context<'a, P, S, BruteForce<S>>,
+    expansion: Expansion<'a, S>,
+    clusters_yielded: usize,
+    /// Whether points are in a yielded cluster.
+    clustered: Vec<bool>,
+    /// The points of clusters that are too small, which become noise unless they are `clustered`.
+    removed: Cluster<S>,
+}
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Returns an iterator that clusters a list of `points` one cluster at a time, e.g., to only
+    /// expand the first few clusters of a large dataset.
+    ///
+    /// Since the noise cluster is only complete once all points have been visited, it is always
+    /// yielded last.
+    pub fn cluster_iter<'a, P: MetricSpace<S>>(
+        &'a self,
+        points: &'a [P],
+    ) -> Result<ClusterIter<'a, P, S>, FuzzyDBSCANError> {
+        self.validate()?;
+        if points.is_empty() {
+            return Err(FuzzyDBSCANError::EmptyInput);
+        }
+        let context = self.build_context(points, None);
+        let expansion = self.expansion(&context, None);
+        Ok(ClusterIter {
+            fuzzy_dbscan: self,
+            context,
+            expansion,
+            clusters_yielded: 0,
+            clustered: vec![false; points.len()],
+            removed: Vec::new(),
+        })
+    }
+}
+
+impl<P: MetricSpace<S>, S: Float + Send + Sync> Iterator for ClusterIter<'_, P, S> {
+    type Item = Cluster<S>;
+
+    fn next(&mut self) -> Option<Cluster<S>> {
+        let fuzzy_dbscan = self.fuzzy_dbscan;
+        while let Some(mut cluster) =
+            fuzzy_dbscan.expand_next_cluster(&self.context, &mut self.expansion)
+        {
+            if fuzzy_dbscan.is_large_enough(&cluster) {
+                for assignment in &mut cluster {
+                    assignment.cluster_id = Some(self.clusters_yielded);
+                    self.clustered[assignment.index] = true;
+                }
+                self.clusters_yielded += 1;
+                return Some(cluster);
+            }
+            self.removed.extend(cluster);
+        }
+        fuzzy_dbscan.add_unassigned_to_noise(&mut self.expansion);
+        if !self.removed.is_empty() {
+            let clustered = core::mem::take(&mut self.clustered);
+            let removed = core::mem::take(&mut self.removed);
+            add_to_noise(&mut self.expansion.noise_cluster, clustered, removed);
+        }
+        if self.expansion.noise_cluster.is_empty() {
+            None
+        } else {
+            Some(core::mem::take(&mut self.expansion.noise_cluster))
+        }
+    }
+}
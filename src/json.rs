@@ -0,0 +1,24 @@
+//! Reading and writing parameters as JSON.
+use alloc::string::String;
+use num_traits::Float;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::FuzzyDBSCAN;
+
+impl<S: Float + Serialize + DeserializeOwned> FuzzyDBSCAN<S> {
+    /// Returns the parameters as pretty-printed JSON, whose keys are the names of the fields.
+    ///
+    /// Note that membership functions are not serialized, thus they are reset to their defaults
+    /// by [`from_json`](Self::from_json).
+    pub fn to_json(&self) -> String {
+        // Serializing numbers and strings to a string cannot fail.
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Reads parameters written by [`to_json`](Self::to_json), where missing optional fields
+    /// take their default values.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
@@ -5,101 +5,863 @@
 //! ```rust
 //! extern crate fuzzy_dbscan;
 //!
-//! #[derive(Debug)]
-//! struct Point {
-//!     x: f64,
-//!     y: f64,
-//! }
-//!
-//! impl fuzzy_dbscan::MetricSpace for Point {
-//!     fn distance(&self, other: &Self) -> f64 {
-//!         ((other.x - self.x).powi(2) + (other.y - self.y).powi(2)).sqrt()
-//!     }
-//! }
+//! use fuzzy_dbscan::Point2D;
 //!
 //! fn main() {
 //!     let points = vec![
-//!         Point { x: 0.0, y: 0.0 },
-//!         Point { x: 100.0, y: 100.0 },
-//!         Point { x: 105.0, y: 105.0 },
-//!         Point { x: 115.0, y: 115.0 },
+//!         Point2D::new(0.0, 0.0),
+//!         Point2D::new(100.0, 100.0),
+//!         Point2D::new(105.0, 105.0),
+//!         Point2D::new(115.0, 115.0),
 //!     ];
 //!
-//!     let fuzzy_dbscan = fuzzy_dbscan::FuzzyDBSCAN {
-//!         eps_min: 10.0,
-//!         eps_max: 20.0,
-//!         pts_min: 1.0,
-//!         pts_max: 2.0,
-//!     };
+//!     let fuzzy_dbscan = fuzzy_dbscan::FuzzyDBSCAN::builder()
+//!         .eps_min(10.0)
+//!         .eps_max(20.0)
+//!         .pts_min(1.0)
+//!         .pts_max(2.0)
+//!         .build()
+//!         .unwrap();
 //!
-//!     println!("{:?}", fuzzy_dbscan.cluster(&points));
+//!     println!("{:?}", fuzzy_dbscan.cluster(&points).unwrap());
 //! }
 //! ```
+//!
+//! Other types of points can be clustered by implementing [`MetricSpace`]. Enable the `nalgebra`
+//! or `ndarray` feature to cluster points of `nalgebra` or views of `ndarray` in Euclidean space.
+//! Enable the `spectral` feature to embed high-dimensional data in fewer dimensions first.
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+extern crate num_traits;
+#[cfg(feature = "std")]
 extern crate wasm_bindgen;
 #[macro_use]
 extern crate serde_derive;
 
+#[cfg(all(feature = "std", target_arch = "wasm32", target_os = "unknown"))]
 use wasm_bindgen::prelude::*;
 
-use std::collections::HashSet;
-use std::f64;
-use std::hash::Hash;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
-fn take_arbitrary<T: Hash + Eq + Copy>(set: &mut HashSet<T>) -> Option<T> {
-    if let Some(key) = set.iter().next().copied() {
-        set.take(&key)
-    } else {
-        None
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use num_traits::Float;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+#[cfg(feature = "bincode")]
+mod bincode_io;
+mod classify;
+mod clusterer;
+mod compressed;
+mod contour;
+#[cfg(feature = "csv")]
+mod csv_io;
+pub mod distance;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod flat;
+pub mod geo;
+mod graph;
+pub mod heuristics;
+mod hierarchy;
+pub mod index;
+mod iter;
+#[cfg(feature = "json")]
+mod json;
+mod matrix;
+pub mod membership;
+mod memory;
+mod merge;
+pub mod metrics;
+mod outlier;
+mod partial;
+mod points;
+mod precomputed;
+#[cfg(feature = "python")]
+mod python;
+pub mod query;
+mod reachability;
+mod result;
+#[cfg(feature = "spectral")]
+mod spectral;
+mod state;
+mod subspace;
+mod summary;
+mod validation;
+
+#[cfg(feature = "bincode")]
+pub use bincode_io::{clusters_from_bincode, clusters_to_bincode, BincodeError};
+pub use clusterer::{ClusterUpdate, Clusterer};
+pub use compressed::{compress_clusters, decompress_clusters, CompressedCluster};
+#[cfg(feature = "csv")]
+pub use csv_io::{read_clusters_csv, write_clusters_csv, CsvError};
+pub use flat::{cluster_to_partition_matrix, defuzzify, FlatAssignment, MultiAssignment};
+pub use hierarchy::{ClusterHierarchy, HierarchyEdge};
+use index::{BruteForce, SpatialIndex};
+pub use iter::ClusterIter;
+pub use matrix::DistanceMatrix;
+use matrix::MatrixPoint;
+use membership::{default_membership, MembershipFn};
+pub use memory::MemoryEstimate;
+pub use merge::{cluster_overlap_matrix, merge_clusters, merge_overlapping_clusters};
+use points::FnPoint;
+pub use points::{CosinePoint, NDPoint, NDPointWith, Point2D, Point2D64, SubspacePoint};
+pub use precomputed::PrecomputedNeighbors;
+/// The version of PyO3 used by the Python bindings.
+#[cfg(feature = "python")]
+pub use pyo3;
+#[cfg(feature = "python")]
+pub use python::PyFuzzyDBSCAN;
+pub use reachability::ReachabilityEntry;
+pub use result::ClusterResult;
+#[cfg(feature = "spectral")]
+pub use spectral::spectral_embedding;
+pub use state::ClusterState;
+pub use subspace::{SubspaceClusters, SubspaceMetric};
+pub use summary::{cluster_centroids, label_statistics, summarize, ClusterSummary, LabelStats};
+pub use validation::{validate_clusters, ValidationError};
+
+/// The reusable buffers of a clustering run, which are reset after expanding each cluster, except
+/// for the cache of neighbors.
+struct Scratch<'a, S> {
+    queued: Vec<bool>,
+    core_labels: Vec<S>,
+    /// The queried neighbors of non-core points, which may be border points of several clusters,
+    /// and are thus kept for the whole run.
+    non_core_neighbors: BTreeMap<usize, Vec<Neighbor<S>>>,
+    visited_count: usize,
+    clusters_found: usize,
+    progress: Option<&'a dyn Fn(ProgressReport)>,
+}
+
+/// The state of expanding clusters one at a time.
+struct Expansion<'a, S> {
+    /// The remaining points to expand clusters from, in order.
+    seeds: vec::IntoIter<usize>,
+    visited: Vec<bool>,
+    /// Whether points have been added to a cluster or the noise cluster.
+    assigned: Vec<bool>,
+    noise_cluster: Cluster<S>,
+    scratch: Scratch<'a, S>,
+}
+
+/// The index, distance, and border label of a neighbor.
+type Neighbor<S> = (usize, S, S);
+
+/// The points queued for expansion into a cluster, in the [expansion order](ExpansionOrder).
+enum ExpansionQueue<S> {
+    Sequential(Vec<usize>),
+    /// The queued points by their precomputed core label and how many points have been queued
+    /// before them, such that the last queued one is taken on ties.
+    HighestDensity(BinaryHeap<QueuedPoint<S>>, usize),
+}
+
+/// A point queued by its core `label`, whose `order` breaks ties.
+struct QueuedPoint<S> {
+    label: S,
+    order: usize,
+    index: usize,
+}
+
+impl<S: Float> Ord for QueuedPoint<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let by_label = self.label.partial_cmp(&other.label);
+        by_label
+            .unwrap_or(Ordering::Equal)
+            .then(self.order.cmp(&other.order))
+    }
+}
+
+impl<S: Float> PartialOrd for QueuedPoint<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: Float> PartialEq for QueuedPoint<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S: Float> Eq for QueuedPoint<S> {}
+
+impl<S: Float> ExpansionQueue<S> {
+    fn new(expansion_order: ExpansionOrder) -> Self {
+        match expansion_order {
+            ExpansionOrder::Sequential => ExpansionQueue::Sequential(Vec::new()),
+            ExpansionOrder::HighestDensity => ExpansionQueue::HighestDensity(BinaryHeap::new(), 0),
+        }
+    }
+
+    /// Queues the point at `index`, whose core label is looked up in the precomputed
+    /// `neighborhoods` if expanding the densest points first.
+    fn push(&mut self, index: usize, neighborhoods: &[(Vec<Neighbor<S>>, S)]) {
+        match self {
+            ExpansionQueue::Sequential(queue) => queue.push(index),
+            ExpansionQueue::HighestDensity(queue, order) => {
+                let label = neighborhoods[index].1;
+                queue.push(QueuedPoint {
+                    label,
+                    order: *order,
+                    index,
+                });
+                *order += 1;
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        match self {
+            ExpansionQueue::Sequential(queue) => queue.pop(),
+            ExpansionQueue::HighestDensity(queue, _) => queue.pop().map(|point| point.index),
+        }
     }
 }
 
-/// A trait to compute distances between points.
-pub trait MetricSpace: Sized {
+/// The read-only state of a clustering run.
+struct Context<'a, P, S, I> {
+    points: &'a [P],
+    weights: Option<&'a [S]>,
+    index: I,
+    /// The precomputed neighbors and core labels of all points, or empty if not precomputed.
+    neighborhoods: Vec<(Vec<Neighbor<S>>, S)>,
+}
+
+#[cfg(feature = "rayon")]
+mod sync {
+    /// A marker trait that requires `Sync` only if the `rayon` feature is enabled.
+    pub trait MaybeSync: Sync {}
+    impl<T: Sync> MaybeSync for T {}
+}
+
+#[cfg(not(feature = "rayon"))]
+mod sync {
+    /// A marker trait that requires `Sync` only if the `rayon` feature is enabled.
+    pub trait MaybeSync {}
+    impl<T> MaybeSync for T {}
+}
+
+/// A trait to compute distances of scalar type `S` between points.
+///
+/// Note that points must be `Sync` if the `rayon` feature is enabled.
+///
+/// # Example
+///
+/// Points with a timestamp can be clustered in space and time by weighting the time difference
+/// with `alpha`, e.g., to separate two cars that passed the same place at different times:
+///
+/// ```rust
+/// use fuzzy_dbscan::{FuzzyDBSCAN, MetricSpace};
+///
+/// struct SpacetimePoint {
+///     x: f32,
+///     y: f32,
+///     t: f32,
+///     alpha: f32,
+/// }
+///
+/// impl MetricSpace<f32> for SpacetimePoint {
+///     fn distance(&self, other: &Self) -> f32 {
+///         let dt = self.alpha * (other.t - self.t);
+///         ((other.x - self.x).powi(2) + (other.y - self.y).powi(2) + dt.powi(2)).sqrt()
+///     }
+/// }
+///
+/// let point = |x: f32, t: f32| SpacetimePoint { x, y: 0.0, t, alpha: 0.1 };
+/// let points = [point(0.0, 0.0), point(1.0, 1.0), point(0.0, 100.0), point(1.0, 101.0)];
+/// let fuzzy_dbscan = FuzzyDBSCAN::<f32> {
+///     eps_min: 1.0,
+///     eps_max: 2.0,
+///     pts_min: 1.0,
+///     pts_max: 2.0,
+///     ..Default::default()
+/// };
+/// assert_eq!(fuzzy_dbscan.cluster(&points).unwrap().cluster_count(), 2);
+/// ```
+pub trait MetricSpace<S: Float = f64>: Sized + sync::MaybeSync {
     /// Returns the distance between `self` and `other`.
-    fn distance(&self, other: &Self) -> f64;
+    fn distance(&self, other: &Self) -> S;
+
+    /// Whether [`distance`](Self::distance) is the square root of
+    /// [`distance_squared`](Self::distance_squared), so that the brute-force index compares squared
+    /// distances with the squared radius and takes the square roots of neighbors only.
+    const SQRT_OF_SQUARED: bool = false;
+
+    /// Returns the squared distance between `self` and `other`.
+    ///
+    /// Override this method if the distance is the square root of a cheaper expression, e.g., for
+    /// Euclidean distances, compute the distance as the square root of this method to keep both
+    /// consistent, and set [`SQRT_OF_SQUARED`](Self::SQRT_OF_SQUARED).
+    fn distance_squared(&self, other: &Self) -> S {
+        self.distance(other).powi(2)
+    }
 }
 
-#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
-#[derive(Deserialize)]
-pub struct JsPoint {
-    x: f64,
-    y: f64,
+impl<S: Float, T: MetricSpace<S>> MetricSpace<S> for &T {
+    const SQRT_OF_SQUARED: bool = T::SQRT_OF_SQUARED;
+
+    fn distance(&self, other: &Self) -> S {
+        (**self).distance(*other)
+    }
+
+    fn distance_squared(&self, other: &Self) -> S {
+        (**self).distance_squared(*other)
+    }
 }
 
-#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
-impl MetricSpace for JsPoint {
-    fn distance(&self, other: &Self) -> f64 {
-        ((other.x - self.x).powi(2) + (other.y - self.y).powi(2)).sqrt() as f64
+impl<S: Float, T: MetricSpace<S>> MetricSpace<S> for Arc<T>
+where
+    Arc<T>: sync::MaybeSync,
+{
+    const SQRT_OF_SQUARED: bool = T::SQRT_OF_SQUARED;
+
+    fn distance(&self, other: &Self) -> S {
+        (**self).distance(other)
+    }
+
+    fn distance_squared(&self, other: &Self) -> S {
+        (**self).distance_squared(other)
     }
 }
 
+/// A trait to access the coordinates of points in a `K`-dimensional Euclidean space.
+pub trait Coordinates<const K: usize, S = f64> {
+    /// Returns the coordinates of `self`.
+    fn coordinates(&self) -> [S; K];
+}
+
 /// A high-level classification, as defined by the FuzzyDBSCAN algorithm.
-#[derive(Debug, PartialEq, Eq, Serialize)]
+///
+/// Categories are serialized as lowercase strings, i.e., `"core"`, `"border"`, and `"noise"`.
+///
+/// New categories may be added in the future, thus matches need a wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum Category {
     Core,
     Border,
     Noise,
 }
 
+impl Category {
+    /// Returns the precedence of the category when combining assignments, highest for core.
+    fn precedence(&self) -> u8 {
+        match self {
+            Category::Core => 2,
+            Category::Border => 1,
+            Category::Noise => 0,
+        }
+    }
+}
+
 /// An element of a [cluster](Cluster).
-#[derive(Debug, Serialize)]
-pub struct Assignment {
+///
+/// Assignments are ordered by descending label, such that sorting a cluster ranks its points
+/// from the highest to the lowest membership. Ties are broken by point index, cluster
+/// identifier, and category.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Assignment<S = f64> {
     /// The point index.
     pub index: usize,
+    /// The identifier of the cluster, which is its position among the clusters returned by
+    /// [`FuzzyDBSCAN::cluster`] (or its identifier in a [`Clusterer`] or [`ClusterState`]), or
+    /// `None` for noise.
+    pub cluster_id: Option<usize>,
     /// A (soft) label between `0.0` and `1.0`.
-    pub label: f64,
+    pub label: S,
     /// A high-level category.
     pub category: Category,
 }
 
+impl<S: PartialOrd> PartialOrd for Assignment<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match other.label.partial_cmp(&self.label)? {
+            Ordering::Equal => Some(
+                self.index
+                    .cmp(&other.index)
+                    .then(self.cluster_id.cmp(&other.cluster_id))
+                    .then(other.category.precedence().cmp(&self.category.precedence())),
+            ),
+            ordering => Some(ordering),
+        }
+    }
+}
+
 /// A group of [assigned](Assignment) points.
-pub type Cluster = Vec<Assignment>;
+pub type Cluster<S = f64> = Vec<Assignment<S>>;
+
+/// The variants of the FuzzyDBSCAN algorithm, depending on which parameter ranges are fuzzy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClusteringMode {
+    /// Classic DBSCAN with crisp cores and borders (`eps_min = eps_max` and `pts_min = pts_max`).
+    Crisp,
+    /// FuzzyCoreDBSCAN with fuzzy cores and crisp borders (`eps_min = eps_max`).
+    FuzzyCore,
+    /// FuzzyBorderDBSCAN with crisp cores and fuzzy borders (`pts_min = pts_max`).
+    FuzzyBorder,
+    /// FuzzyDBSCAN with fuzzy cores and borders.
+    Full,
+}
 
-/// An instance of the FuzzyDBSCAN algorithm.
+/// How the label of a border point is derived from the core points in its neighborhood.
 ///
-/// Note that when setting `eps_min = eps_max` and `pts_min = pts_max` the algorithm will reduce to classic DBSCAN.
-#[wasm_bindgen]
-pub struct FuzzyDBSCAN {
+/// Each core point contributes the minimum of its own label and its distance membership to the
+/// border point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BorderLabelMode {
+    /// The label is the lowest contribution of any core point.
+    #[default]
+    MinCore,
+    /// The label is the highest contribution of any core point, i.e., of its closest core point.
+    MaxCore,
+}
+
+/// The order in which points are taken to seed and expand clusters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExpansionOrder {
+    /// Points are seeded by index and neighbors are expanded last queued, first taken.
+    #[default]
+    Sequential,
+    /// Points with the highest core label are taken first, which requires computing all
+    /// neighborhoods upfront. Since clusters are seeded by core points, border points are never
+    /// mistaken for noise.
+    HighestDensity,
+}
+
+/// How the density of a point is aggregated from its neighborhood.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DensityMode {
+    /// The density is one plus the sum of the distance memberships of all neighbors.
+    #[default]
+    FuzzyWeighted,
+    /// The density is one plus the number of neighbors within `eps_min`, as in classic DBSCAN,
+    /// whereas border labels still depend on distances between `eps_min` and `eps_max`.
+    CrispInner,
+}
+
+/// An instance of the FuzzyDBSCAN algorithm with parameters of scalar type `S`.
+///
+/// Note that when setting `eps_min = eps_max` and `pts_min = pts_max` the algorithm will reduce to classic DBSCAN,
+/// see [`ClusteringMode`].
+#[derive(Serialize, Deserialize)]
+#[serde(bound(serialize = "S: serde::Serialize"))]
+#[serde(bound(deserialize = "S: Float + serde::Deserialize<'de>"))]
+pub struct FuzzyDBSCAN<S = f64> {
+    /// The minimum fuzzy local neighborhood radius.
+    pub eps_min: S,
+    /// The maximum fuzzy local neighborhood radius.
+    pub eps_max: S,
+    /// The minimum fuzzy neighborhood density (number of points).
+    pub pts_min: S,
+    /// The maximum fuzzy neighborhood density (number of points).
+    pub pts_max: S,
+    /// The minimum number of points per cluster, smaller clusters are reclassified as noise.
+    pub min_cluster_size: usize,
+    /// The membership function of points in the core, evaluated for their density between
+    /// `pts_min` and `pts_max`.
+    #[serde(skip, default = "default_membership")]
+    pub core_membership: Box<dyn MembershipFn<S>>,
+    /// The membership function of points in the border, evaluated for their negated distance
+    /// between `-eps_max` and `-eps_min`.
+    #[serde(skip, default = "default_membership")]
+    pub border_membership: Box<dyn MembershipFn<S>>,
+    /// The number of visited points between [progress reports](FuzzyDBSCAN::cluster_with_progress),
+    /// or `0` to only report when all points have been visited.
+    #[serde(default = "default_progress_interval")]
+    pub progress_interval: usize,
+    /// How the labels of border points are derived from the core points in their neighborhood.
+    #[serde(default)]
+    pub border_label_mode: BorderLabelMode,
+    /// The order in which points are taken to seed and expand clusters.
+    #[serde(default)]
+    pub expansion_order: ExpansionOrder,
+    /// How the density of a point is aggregated from its neighborhood.
+    #[serde(default)]
+    pub density_mode: DensityMode,
+    /// Whether a point counts itself towards its density, such that a point without neighbors
+    /// has a density of `1`, as opposed to `0`.
+    #[serde(default = "default_self_included_in_density")]
+    pub self_included_in_density: bool,
+    /// The core label that points must exceed to be core points, otherwise they are border or
+    /// noise points, e.g., to suppress spurious small clusters of low-confidence cores.
+    #[serde(default = "S::zero")]
+    pub core_threshold: S,
+    /// The label that border points must reach to be assigned to a cluster, otherwise they are
+    /// noise points unless assigned to another cluster.
+    #[serde(default = "S::zero")]
+    pub border_threshold: S,
+}
+
+fn default_progress_interval() -> usize {
+    100
+}
+
+fn default_self_included_in_density() -> bool {
+    true
+}
+
+/// Returns whether a core or border `threshold` is between `0` and `1`.
+fn is_threshold<S: Float>(threshold: S) -> bool {
+    threshold >= S::zero() && threshold <= S::one()
+}
+
+/// Checks whether the parameters of `fuzzy_dbscan` are valid.
+fn validate_parameters<S: Float>(fuzzy_dbscan: &FuzzyDBSCAN<S>) -> Result<(), FuzzyDBSCANError> {
+    let FuzzyDBSCAN {
+        eps_min,
+        eps_max,
+        pts_min,
+        pts_max,
+        ..
+    } = *fuzzy_dbscan;
+    if !eps_min.is_finite() || !eps_max.is_finite() {
+        Err(FuzzyDBSCANError::EpsNotFinite)
+    } else if !pts_min.is_finite() || !pts_max.is_finite() {
+        Err(FuzzyDBSCANError::PtsNotFinite)
+    } else if eps_min < S::zero() || eps_min > eps_max {
+        Err(FuzzyDBSCANError::InvalidEpsRange)
+    } else if pts_min < S::zero() || pts_min > pts_max {
+        Err(FuzzyDBSCANError::InvalidPtsRange)
+    } else if !is_threshold(fuzzy_dbscan.core_threshold)
+        || !is_threshold(fuzzy_dbscan.border_threshold)
+    {
+        Err(FuzzyDBSCANError::InvalidThreshold)
+    } else {
+        Ok(())
+    }
+}
+
+/// The default parameters suit data whose features are standardized, i.e., have a mean of `0`
+/// and a standard deviation of `1`, such that neighbors are about `1` apart: points within `0.5`
+/// fully belong to a neighborhood, which becomes a full core with `5` points (including the point
+/// itself) and a partial core with more than `2` points. For other data, scale `eps_min` and
+/// `eps_max` with the typical distance between neighbors, e.g., as estimated by
+/// [`heuristics::k_distance_graph`], and `pts_min` and `pts_max` with the density of the data.
+impl<S: Float> Default for FuzzyDBSCAN<S> {
+    fn default() -> Self {
+        FuzzyDBSCAN {
+            eps_min: S::from(0.5).unwrap(),
+            eps_max: S::one(),
+            pts_min: S::from(2.0).unwrap(),
+            pts_max: S::from(5.0).unwrap(),
+            min_cluster_size: 1,
+            core_membership: default_membership(),
+            border_membership: default_membership(),
+            progress_interval: default_progress_interval(),
+            border_label_mode: BorderLabelMode::default(),
+            expansion_order: ExpansionOrder::default(),
+            density_mode: DensityMode::default(),
+            self_included_in_density: default_self_included_in_density(),
+            core_threshold: S::zero(),
+            border_threshold: S::zero(),
+        }
+    }
+}
+
+/// The progress of a clustering run, as reported by [`FuzzyDBSCAN::cluster_with_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgressReport {
+    /// The number of points visited so far.
+    pub points_visited: usize,
+    /// The total number of points.
+    pub total_points: usize,
+    /// The number of clusters found so far, including small clusters that might be removed.
+    pub clusters_found: usize,
+}
+
+/// An error describing why a set of [`FuzzyDBSCAN`] parameters is invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidParametersError {
+    /// `eps_min` is greater than `eps_max`.
+    EpsRangeInverted,
+    /// `pts_min` is greater than `pts_max`.
+    PtsRangeInverted,
+    /// `eps_min` or `eps_max` is negative.
+    NegativeEps,
+    /// `pts_min` or `pts_max` is negative.
+    NegativePts,
+    /// A parameter is NaN (or has not been set).
+    NaNParameter,
+    /// `eps_min`, `eps_max`, `pts_min`, or `pts_max` is infinite.
+    InfiniteParameter,
+    /// `core_threshold` or `border_threshold` is not between `0` and `1`.
+    ThresholdOutOfRange,
+}
+
+impl fmt::Display for InvalidParametersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            InvalidParametersError::EpsRangeInverted => "eps_min is greater than eps_max",
+            InvalidParametersError::PtsRangeInverted => "pts_min is greater than pts_max",
+            InvalidParametersError::NegativeEps => "eps_min or eps_max is negative",
+            InvalidParametersError::NegativePts => "pts_min or pts_max is negative",
+            InvalidParametersError::NaNParameter => "a parameter is NaN or has not been set",
+            InvalidParametersError::InfiniteParameter => "a parameter is infinite",
+            InvalidParametersError::ThresholdOutOfRange => {
+                "core_threshold or border_threshold is not between 0 and 1"
+            }
+        };
+        f.write_str(message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for InvalidParametersError {}
+
+/// An error describing why clustering failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FuzzyDBSCANError {
+    /// `eps_min` is negative or greater than `eps_max`.
+    InvalidEpsRange,
+    /// `pts_min` is negative or greater than `pts_max`.
+    InvalidPtsRange,
+    /// `eps_min` or `eps_max` is NaN or infinite.
+    EpsNotFinite,
+    /// `pts_min` or `pts_max` is NaN or infinite.
+    PtsNotFinite,
+    /// There are no points to cluster.
+    EmptyInput,
+    /// The number of weights does not match the number of points.
+    WeightsLengthMismatch,
+    /// `core_threshold` or `border_threshold` is not between `0` and `1`.
+    InvalidThreshold,
+    /// The number of nearest neighbors `k` is zero or not less than the number of points.
+    InvalidNeighborCount,
+}
+
+impl fmt::Display for FuzzyDBSCANError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            FuzzyDBSCANError::InvalidEpsRange => "eps_min is negative or greater than eps_max",
+            FuzzyDBSCANError::InvalidPtsRange => "pts_min is negative or greater than pts_max",
+            FuzzyDBSCANError::EpsNotFinite => "eps_min or eps_max is not finite",
+            FuzzyDBSCANError::PtsNotFinite => "pts_min or pts_max is not finite",
+            FuzzyDBSCANError::EmptyInput => "there are no points to cluster",
+            FuzzyDBSCANError::WeightsLengthMismatch => {
+                "the number of weights does not match the number of points"
+            }
+            FuzzyDBSCANError::InvalidThreshold => {
+                "core_threshold or border_threshold is not between 0 and 1"
+            }
+            FuzzyDBSCANError::InvalidNeighborCount => {
+                "k is zero or not less than the number of points"
+            }
+        };
+        f.write_str(message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for FuzzyDBSCANError {}
+
+/// A builder to create a validated instance of the [FuzzyDBSCAN] algorithm.
+///
+/// # Example
+///
+/// ```rust
+/// let fuzzy_dbscan = fuzzy_dbscan::FuzzyDBSCAN::builder()
+///     .eps_min(10.0)
+///     .eps_max(20.0)
+///     .pts_min(1.0)
+///     .pts_max(2.0)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct FuzzyDBSCANBuilder<S = f64> {
+    eps_min: S,
+    eps_max: S,
+    pts_min: S,
+    pts_max: S,
+    min_cluster_size: usize,
+    core_membership: Box<dyn MembershipFn<S>>,
+    border_membership: Box<dyn MembershipFn<S>>,
+    progress_interval: usize,
+    border_label_mode: BorderLabelMode,
+    expansion_order: ExpansionOrder,
+    density_mode: DensityMode,
+    self_included_in_density: bool,
+    core_threshold: S,
+    border_threshold: S,
+}
+
+impl<S: Float> Default for FuzzyDBSCANBuilder<S> {
+    fn default() -> Self {
+        FuzzyDBSCANBuilder {
+            eps_min: S::nan(),
+            eps_max: S::nan(),
+            pts_min: S::nan(),
+            pts_max: S::nan(),
+            min_cluster_size: 1,
+            core_membership: default_membership(),
+            border_membership: default_membership(),
+            progress_interval: default_progress_interval(),
+            border_label_mode: BorderLabelMode::default(),
+            expansion_order: ExpansionOrder::default(),
+            density_mode: DensityMode::default(),
+            self_included_in_density: default_self_included_in_density(),
+            core_threshold: S::zero(),
+            border_threshold: S::zero(),
+        }
+    }
+}
+
+impl<S: Float> FuzzyDBSCANBuilder<S> {
+    /// Creates a new builder with all parameters unset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the minimum fuzzy local neighborhood radius.
+    pub fn eps_min(mut self, eps_min: S) -> Self {
+        self.eps_min = eps_min;
+        self
+    }
+
+    /// Sets the maximum fuzzy local neighborhood radius.
+    pub fn eps_max(mut self, eps_max: S) -> Self {
+        self.eps_max = eps_max;
+        self
+    }
+
+    /// Sets the minimum fuzzy neighborhood density (number of points).
+    pub fn pts_min(mut self, pts_min: S) -> Self {
+        self.pts_min = pts_min;
+        self
+    }
+
+    /// Sets the maximum fuzzy neighborhood density (number of points).
+    pub fn pts_max(mut self, pts_max: S) -> Self {
+        self.pts_max = pts_max;
+        self
+    }
+
+    /// Sets the minimum number of points per cluster (defaults to `1`).
+    pub fn min_cluster_size(mut self, min_cluster_size: usize) -> Self {
+        self.min_cluster_size = min_cluster_size;
+        self
+    }
+
+    /// Sets the membership function of core points (defaults to [linear](membership::LinearMembership)).
+    pub fn core_membership(mut self, core_membership: impl MembershipFn<S> + 'static) -> Self {
+        self.core_membership = Box::new(core_membership);
+        self
+    }
+
+    /// Sets the membership function of border points (defaults to [linear](membership::LinearMembership)).
+    pub fn border_membership(mut self, border_membership: impl MembershipFn<S> + 'static) -> Self {
+        self.border_membership = Box::new(border_membership);
+        self
+    }
+
+    /// Sets the number of visited points between progress reports (defaults to `100`).
+    pub fn progress_interval(mut self, progress_interval: usize) -> Self {
+        self.progress_interval = progress_interval;
+        self
+    }
+
+    /// Sets how the labels of border points are derived (defaults to
+    /// [`MinCore`](BorderLabelMode::MinCore)).
+    pub fn border_label_mode(mut self, border_label_mode: BorderLabelMode) -> Self {
+        self.border_label_mode = border_label_mode;
+        self
+    }
+
+    /// Sets the order in which points are taken to seed and expand clusters (defaults to
+    /// [`Sequential`](ExpansionOrder::Sequential)).
+    pub fn expansion_order(mut self, expansion_order: ExpansionOrder) -> Self {
+        self.expansion_order = expansion_order;
+        self
+    }
+
+    /// Sets how the density of a point is aggregated (defaults to
+    /// [`FuzzyWeighted`](DensityMode::FuzzyWeighted)).
+    pub fn density_mode(mut self, density_mode: DensityMode) -> Self {
+        self.density_mode = density_mode;
+        self
+    }
+
+    /// Sets whether a point counts itself towards its density (defaults to `true`).
+    pub fn self_included_in_density(mut self, self_included_in_density: bool) -> Self {
+        self.self_included_in_density = self_included_in_density;
+        self
+    }
+
+    /// Sets the core label that core points must exceed (defaults to `0`).
+    pub fn core_threshold(mut self, core_threshold: S) -> Self {
+        self.core_threshold = core_threshold;
+        self
+    }
+
+    /// Sets the label that border points must reach (defaults to `0`).
+    pub fn border_threshold(mut self, border_threshold: S) -> Self {
+        self.border_threshold = border_threshold;
+        self
+    }
+
+    /// Validates the parameters and creates an instance of the algorithm.
+    pub fn build(self) -> Result<FuzzyDBSCAN<S>, InvalidParametersError> {
+        let parameters = [self.eps_min, self.eps_max, self.pts_min, self.pts_max];
+        if parameters.iter().any(|parameter| parameter.is_nan()) {
+            return Err(InvalidParametersError::NaNParameter);
+        }
+        if self.eps_min < S::zero() || self.eps_max < S::zero() {
+            return Err(InvalidParametersError::NegativeEps);
+        }
+        if self.pts_min < S::zero() || self.pts_max < S::zero() {
+            return Err(InvalidParametersError::NegativePts);
+        }
+        if self.eps_min > self.eps_max {
+            return Err(InvalidParametersError::EpsRangeInverted);
+        }
+        if self.pts_min > self.pts_max {
+            return Err(InvalidParametersError::PtsRangeInverted);
+        }
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min: self.eps_min,
+            eps_max: self.eps_max,
+            pts_min: self.pts_min,
+            pts_max: self.pts_max,
+            min_cluster_size: self.min_cluster_size,
+            core_membership: self.core_membership,
+            border_membership: self.border_membership,
+            progress_interval: self.progress_interval,
+            border_label_mode: self.border_label_mode,
+            expansion_order: self.expansion_order,
+            density_mode: self.density_mode,
+            self_included_in_density: self.self_included_in_density,
+            core_threshold: self.core_threshold,
+            border_threshold: self.border_threshold,
+        };
+        // Check everything else clustering would reject, e.g., infinite parameters.
+        validate_parameters(&fuzzy_dbscan).map_err(|error| match error {
+            FuzzyDBSCANError::EpsNotFinite | FuzzyDBSCANError::PtsNotFinite => {
+                InvalidParametersError::InfiniteParameter
+            }
+            FuzzyDBSCANError::InvalidEpsRange => InvalidParametersError::EpsRangeInverted,
+            FuzzyDBSCANError::InvalidPtsRange => InvalidParametersError::PtsRangeInverted,
+            FuzzyDBSCANError::InvalidThreshold => InvalidParametersError::ThresholdOutOfRange,
+            _ => unreachable!("only parameters are validated"),
+        })?;
+        Ok(fuzzy_dbscan)
+    }
+}
+
+/// An instance of the FuzzyDBSCAN algorithm for JavaScript.
+#[cfg(all(feature = "std", target_arch = "wasm32", target_os = "unknown"))]
+#[wasm_bindgen(js_name = FuzzyDBSCAN)]
+pub struct JsFuzzyDBSCAN {
     /// The minimum fuzzy local neighborhood radius.
     pub eps_min: f64,
     /// The maximum fuzzy local neighborhood radius.
@@ -108,64 +870,456 @@ pub struct FuzzyDBSCAN {
     pub pts_min: f64,
     /// The maximum fuzzy neighborhood density (number of points).
     pub pts_max: f64,
+    /// The minimum number of points per cluster, smaller clusters are reclassified as noise.
+    pub min_cluster_size: usize,
+    /// The number of visited points between progress reports.
+    pub progress_interval: usize,
+    /// The core label that core points must exceed.
+    pub core_threshold: f64,
+    /// The label that border points must reach.
+    pub border_threshold: f64,
 }
 
-#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
-#[wasm_bindgen]
-impl FuzzyDBSCAN {
-    /// Creates a new instance of the algorithm.
+#[cfg(all(feature = "std", target_arch = "wasm32", target_os = "unknown"))]
+impl Default for JsFuzzyDBSCAN {
+    fn default() -> Self {
+        let fuzzy_dbscan = FuzzyDBSCAN::default();
+        JsFuzzyDBSCAN {
+            eps_min: fuzzy_dbscan.eps_min,
+            eps_max: fuzzy_dbscan.eps_max,
+            pts_min: fuzzy_dbscan.pts_min,
+            pts_max: fuzzy_dbscan.pts_max,
+            min_cluster_size: fuzzy_dbscan.min_cluster_size,
+            progress_interval: 500,
+            core_threshold: fuzzy_dbscan.core_threshold,
+            border_threshold: fuzzy_dbscan.border_threshold,
+        }
+    }
+}
+
+#[cfg(all(feature = "std", target_arch = "wasm32", target_os = "unknown"))]
+#[wasm_bindgen(js_class = FuzzyDBSCAN)]
+impl JsFuzzyDBSCAN {
+    /// Creates a new instance of the algorithm with the [default](FuzzyDBSCAN::default)
+    /// parameters.
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        FuzzyDBSCAN {
-            eps_min: f64::NAN,
-            eps_max: f64::NAN,
-            pts_min: f64::NAN,
-            pts_max: f64::NAN,
+        Self::default()
+    }
+
+    /// Clusters points whose `dimensions` coordinates are stored consecutively in `coordinates`,
+    /// e.g., `[x0, y0, x1, y1, ...]` for 2D points, throwing an exception if that fails.
+    ///
+    /// If `js_callback` is a function, it is called with `{ visited, total }` every
+    /// `progress_interval` visited points and once all points have been visited.
+    pub fn cluster(
+        &self,
+        coordinates: &[f64],
+        dimensions: usize,
+        js_callback: JsValue,
+    ) -> Result<JsClusters, JsValue> {
+        if dimensions == 0 || coordinates.len() % dimensions != 0 {
+            return Err(JsValue::from_str(
+                "the number of coordinates is not a multiple of the dimensions",
+            ));
+        }
+        let points: Vec<NDPoint> = coordinates
+            .chunks_exact(dimensions)
+            .map(|point| NDPoint::new(point.to_vec()))
+            .collect();
+        let parameters = [self.eps_min, self.eps_max, self.pts_min, self.pts_max];
+        if parameters.iter().any(|parameter| parameter.is_nan()) {
+            web_sys::console::warn_1(&JsValue::from_str(
+                "FuzzyDBSCAN: eps_min, eps_max, pts_min, and pts_max must not be NaN",
+            ));
+        }
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min: self.eps_min,
+            eps_max: self.eps_max,
+            pts_min: self.pts_min,
+            pts_max: self.pts_max,
+            min_cluster_size: self.min_cluster_size,
+            core_membership: Box::new(membership::LinearMembership),
+            border_membership: Box::new(membership::LinearMembership),
+            progress_interval: self.progress_interval,
+            border_label_mode: BorderLabelMode::default(),
+            expansion_order: ExpansionOrder::default(),
+            density_mode: DensityMode::default(),
+            self_included_in_density: default_self_included_in_density(),
+            core_threshold: self.core_threshold,
+            border_threshold: self.border_threshold,
+        };
+        let callback_error = std::cell::Cell::new(None);
+        let clusters = match js_callback.dyn_ref::<js_sys::Function>() {
+            Some(function) => fuzzy_dbscan.cluster_with_progress(&points, |report| {
+                let progress = js_sys::Object::new();
+                let visited = JsValue::from(report.points_visited as u32);
+                let total = JsValue::from(report.total_points as u32);
+                // Setting properties of a new object cannot fail.
+                js_sys::Reflect::set(&progress, &"visited".into(), &visited).unwrap();
+                js_sys::Reflect::set(&progress, &"total".into(), &total).unwrap();
+                if let Err(error) = function.call1(&JsValue::NULL, &progress) {
+                    callback_error.set(Some(error));
+                }
+            }),
+            None => fuzzy_dbscan.cluster(&points),
         }
+        .map_err(|error| JsValue::from_str(&error.to_string()))?;
+        if let Some(error) = callback_error.into_inner() {
+            return Err(error);
+        }
+        let mut js_clusters = JsClusters {
+            cluster_ids: Vec::new(),
+            indices: Vec::new(),
+            labels: Vec::new(),
+            categories: Vec::new(),
+        };
+        for a in clusters.iter().flatten() {
+            js_clusters
+                .cluster_ids
+                .push(a.cluster_id.map_or(-1, |cluster_id| cluster_id as i32));
+            js_clusters.indices.push(a.index as u32);
+            js_clusters.labels.push(a.label as f32);
+            js_clusters.categories.push(match a.category {
+                Category::Core => 0,
+                Category::Border => 1,
+                Category::Noise => 2,
+            });
+        }
+        Ok(js_clusters)
+    }
+}
+
+/// Checks whether the parameters are valid and returns a description of the error otherwise.
+#[cfg(all(feature = "std", target_arch = "wasm32", target_os = "unknown"))]
+#[wasm_bindgen]
+pub fn validate_params(eps_min: f64, eps_max: f64, pts_min: f64, pts_max: f64) -> Option<String> {
+    FuzzyDBSCAN::builder()
+        .eps_min(eps_min)
+        .eps_max(eps_max)
+        .pts_min(pts_min)
+        .pts_max(pts_max)
+        .build()
+        .err()
+        .map(|error| error.to_string())
+}
+
+/// The assignments of all clusters for JavaScript, stored in parallel arrays.
+#[cfg(all(feature = "std", target_arch = "wasm32", target_os = "unknown"))]
+#[wasm_bindgen(js_name = Clusters)]
+pub struct JsClusters {
+    cluster_ids: Vec<i32>,
+    indices: Vec<u32>,
+    labels: Vec<f32>,
+    categories: Vec<u8>,
+}
+
+#[cfg(all(feature = "std", target_arch = "wasm32", target_os = "unknown"))]
+#[wasm_bindgen(js_class = Clusters)]
+impl JsClusters {
+    /// Returns the cluster identifiers as an `Int32Array`, where noise is `-1`.
+    #[wasm_bindgen(getter, js_name = clusterIds)]
+    pub fn cluster_ids(&self) -> Vec<i32> {
+        self.cluster_ids.clone()
     }
 
-    /// Clusters a list of `js_points`.
-    pub fn cluster(&self, js_points: JsValue) -> JsValue {
-        let points: Vec<JsPoint> = js_points.into_serde().unwrap();
-        let clusters = self.fuzzy_dbscan(&points);
-        JsValue::from_serde(&clusters).unwrap()
+    /// Returns the point indices as an `Uint32Array`.
+    #[wasm_bindgen(getter)]
+    pub fn indices(&self) -> Vec<u32> {
+        self.indices.clone()
+    }
+
+    /// Returns the labels as a `Float32Array`.
+    #[wasm_bindgen(getter)]
+    pub fn labels(&self) -> Vec<f32> {
+        self.labels.clone()
+    }
+
+    /// Returns the categories as an `Uint8Array`, where core is `0`, border is `1`, and noise
+    /// is `2`.
+    #[wasm_bindgen(getter)]
+    pub fn categories(&self) -> Vec<u8> {
+        self.categories.clone()
     }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-impl FuzzyDBSCAN {
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Creates a [builder](FuzzyDBSCANBuilder), which is the recommended way to create an
+    /// instance of the algorithm.
+    pub fn builder() -> FuzzyDBSCANBuilder<S> {
+        FuzzyDBSCANBuilder::new()
+    }
+
+    /// Creates an instance of the algorithm with the [default](Self::default) parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an instance of the algorithm in the given `mode`.
+    ///
+    /// Crisp parameters are set to `eps` and `pts`, fuzzy parameters range from half of `eps`
+    /// and `pts` up to `eps` and `pts`, respectively.
+    pub fn with_mode(mode: ClusteringMode, eps: S, pts: S) -> Self {
+        let half = |value: S| value / (S::one() + S::one());
+        let (eps_min, pts_min) = match mode {
+            ClusteringMode::Crisp => (eps, pts),
+            ClusteringMode::FuzzyCore => (eps, half(pts)),
+            ClusteringMode::FuzzyBorder => (half(eps), pts),
+            ClusteringMode::Full => (half(eps), half(pts)),
+        };
+        FuzzyDBSCAN {
+            eps_min,
+            eps_max: eps,
+            pts_min,
+            pts_max: pts,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the mode that the parameters correspond to.
+    pub fn mode(&self) -> ClusteringMode {
+        match (self.eps_min == self.eps_max, self.pts_min == self.pts_max) {
+            (true, true) => ClusteringMode::Crisp,
+            (true, false) => ClusteringMode::FuzzyCore,
+            (false, true) => ClusteringMode::FuzzyBorder,
+            (false, false) => ClusteringMode::Full,
+        }
+    }
+
+    /// Checks whether the parameters are valid.
+    pub fn validate(&self) -> Result<(), FuzzyDBSCANError> {
+        validate_parameters(self)
+    }
+
     /// Clusters a list of `points`.
-    pub fn cluster<P: MetricSpace>(&self, points: &[P]) -> Vec<Cluster> {
-        self.fuzzy_dbscan(points)
+    pub fn cluster<P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+    ) -> Result<ClusterResult<S>, FuzzyDBSCANError> {
+        self.checked_fuzzy_dbscan::<BruteForce<S>, _>(points, None)
+    }
+
+    /// Clusters a list of `points` and reports the progress to `callback` every
+    /// `progress_interval` visited points and once all points have been visited.
+    ///
+    /// Note that if the `rayon` feature is enabled, all neighborhoods are computed before the
+    /// first report.
+    pub fn cluster_with_progress<P: MetricSpace<S>, F: Fn(ProgressReport)>(
+        &self,
+        points: &[P],
+        callback: F,
+    ) -> Result<ClusterResult<S>, FuzzyDBSCANError> {
+        self.checked_fuzzy_dbscan::<BruteForce<S>, _>(points, Some(&callback))
+    }
+
+    /// Clusters a list of `points` using the [spatial index](SpatialIndex) `I` to find neighbors.
+    pub fn cluster_with_index<I: SpatialIndex<P, S>, P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+    ) -> Result<ClusterResult<S>, FuzzyDBSCANError> {
+        self.checked_fuzzy_dbscan::<I, _>(points, None)
+    }
+
+    /// Clusters the points of a precomputed distance `matrix`.
+    pub fn cluster_with_matrix(
+        &self,
+        matrix: &DistanceMatrix<S>,
+    ) -> Result<ClusterResult<S>, FuzzyDBSCANError> {
+        let points: Vec<_> = (0..matrix.len())
+            .map(|index| MatrixPoint { matrix, index })
+            .collect();
+        self.checked_fuzzy_dbscan::<BruteForce<S>, _>(&points, None)
+    }
+
+    /// Clusters a list of `points` using `distance_fn` to compute their distances, without
+    /// implementing [`MetricSpace`].
+    ///
+    /// Note that `points` and `distance_fn` must be `Sync` if the `rayon` feature is enabled.
+    pub fn cluster_with_fn<P: sync::MaybeSync, F: Fn(&P, &P) -> S + sync::MaybeSync>(
+        &self,
+        points: &[P],
+        distance_fn: F,
+    ) -> Result<ClusterResult<S>, FuzzyDBSCANError> {
+        let points: Vec<_> = points
+            .iter()
+            .map(|point| FnPoint {
+                point,
+                distance_fn: &distance_fn,
+            })
+            .collect();
+        self.checked_fuzzy_dbscan::<BruteForce<S>, _>(&points, None)
+    }
+
+    /// Clusters a list of `points` using `distance_fn` to compute their distances in parallel.
+    ///
+    /// This is the same as [`cluster_with_fn`](Self::cluster_with_fn), which also computes
+    /// distances in parallel if the `rayon` feature is enabled, but states the bounds explicitly.
+    #[cfg(feature = "rayon")]
+    pub fn cluster_with_fn_parallel<P: Sync, F: Fn(&P, &P) -> S + Sync>(
+        &self,
+        points: &[P],
+        distance_fn: F,
+    ) -> Result<ClusterResult<S>, FuzzyDBSCANError> {
+        self.cluster_with_fn(points, distance_fn)
+    }
+
+    /// Clusters a list of `points`, where each neighbor contributes to the density of a point
+    /// according to its weight in `weights`.
+    ///
+    /// Note that clustering with all weights set to `1.0` is equivalent to [`cluster`](Self::cluster).
+    pub fn cluster_weighted<P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+        weights: &[S],
+    ) -> Result<ClusterResult<S>, FuzzyDBSCANError> {
+        if weights.len() != points.len() {
+            return Err(FuzzyDBSCANError::WeightsLengthMismatch);
+        }
+        self.validate()?;
+        if points.is_empty() {
+            return Err(FuzzyDBSCANError::EmptyInput);
+        }
+        Ok(self
+            .fuzzy_dbscan::<BruteForce<S>, _>(points, Some(weights), None)
+            .into())
+    }
+
+    /// Computes the [core label](Self::compute_core_label) of each of the `points` without
+    /// expanding any clusters, i.e., `0` for non-core points (including those with a label up to
+    /// `core_threshold`) and up to `1` for core points.
+    pub fn dry_run<P: MetricSpace<S>>(&self, points: &[P]) -> Result<Vec<S>, FuzzyDBSCANError> {
+        self.validate()?;
+        let context = Context {
+            points,
+            weights: None,
+            index: BruteForce::build(points, self.eps_max),
+            neighborhoods: Vec::new(),
+        };
+        Ok(self
+            .precompute_neighborhoods(&context)
+            .into_iter()
+            .map(|(_, density)| match self.mu_min_p(density) {
+                label if self.is_core_label(label) => label,
+                _ => S::zero(),
+            })
+            .collect())
+    }
+
+    /// Computes the fuzzy density of the point at `point_index`, i.e., one (unless
+    /// `self_included_in_density` is `false`) plus the sum of the [border
+    /// labels](Self::compute_border_label) of all its neighbors within `eps_max` (or the number of
+    /// neighbors within `eps_min`, depending on the [density mode](DensityMode)).
+    pub fn compute_density<P: MetricSpace<S>>(&self, points: &[P], point_index: usize) -> S {
+        let context = Context {
+            points,
+            weights: None,
+            index: BruteForce::build(points, self.eps_max),
+            neighborhoods: Vec::new(),
+        };
+        let neighbors = self.fuzzy_region_query(point_index, &context);
+        self.density(&neighbors, &context)
+    }
+
+    /// Returns the sorted indices, distances, and [border labels](Self::compute_border_label) of
+    /// all neighbors of the point at `point_index` within `eps_max`.
+    pub fn region_query_with_distances<P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+        point_index: usize,
+    ) -> Vec<(usize, S, S)> {
+        let context = Context {
+            points,
+            weights: None,
+            index: BruteForce::build(points, self.eps_max),
+            neighborhoods: Vec::new(),
+        };
+        self.fuzzy_region_query(point_index, &context)
+    }
+
+    /// Computes the label of a core point with the given `density`, which is zero for noise.
+    pub fn compute_core_label(&self, density: S) -> S {
+        self.mu_min_p(density)
+    }
+
+    /// Computes the label of a border point `a` with respect to a core point `b`, based on their
+    /// distance.
+    pub fn compute_border_label<P: MetricSpace<S>>(&self, a: &P, b: &P) -> S {
+        self.mu_distance(a, b)
+    }
+
+    fn checked_fuzzy_dbscan<I: SpatialIndex<P, S>, P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+        progress: Option<&dyn Fn(ProgressReport)>,
+    ) -> Result<ClusterResult<S>, FuzzyDBSCANError> {
+        self.validate()?;
+        if points.is_empty() {
+            return Err(FuzzyDBSCANError::EmptyInput);
+        }
+        Ok(self.fuzzy_dbscan::<I, _>(points, None, progress).into())
     }
-}
 
-impl FuzzyDBSCAN {
-    fn fuzzy_dbscan<P: MetricSpace>(&self, points: &[P]) -> Vec<Cluster> {
+    fn fuzzy_dbscan<I: SpatialIndex<P, S>, P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+        weights: Option<&[S]>,
+        progress: Option<&dyn Fn(ProgressReport)>,
+    ) -> Vec<Cluster<S>> {
+        let context = self.build_context::<I, _>(points, weights);
+        self.expand_clusters(&context, progress)
+    }
+
+    fn build_context<'a, I: SpatialIndex<P, S>, P: MetricSpace<S>>(
+        &self,
+        points: &'a [P],
+        weights: Option<&'a [S]>,
+    ) -> Context<'a, P, S, I> {
+        let mut context = Context {
+            points,
+            weights,
+            index: I::build(points, self.eps_max),
+            neighborhoods: Vec::new(),
+        };
+        // Distances and densities do not depend on the order of expansion, thus compute them
+        // in parallel upfront, which is also required to expand the densest points first.
+        if cfg!(feature = "rayon") || self.expansion_order == ExpansionOrder::HighestDensity {
+            context.neighborhoods = self
+                .precompute_neighborhoods(&context)
+                .into_iter()
+                .map(|(neighbors, density)| (neighbors, self.mu_min_p(density)))
+                .collect();
+        }
+        context
+    }
+
+    /// Expands clusters from all unvisited points in order.
+    fn expand_clusters<I: SpatialIndex<P, S>, P: MetricSpace<S>>(
+        &self,
+        context: &Context<P, S, I>,
+        progress: Option<&dyn Fn(ProgressReport)>,
+    ) -> Vec<Cluster<S>> {
+        let mut expansion = self.expansion(context, progress);
         let mut clusters = Vec::new();
-        let mut noise_cluster = Vec::new();
-        let mut visited = vec![false; points.len()];
-        for point_index in 0..points.len() {
-            if visited[point_index] {
-                continue;
+        while let Some(cluster) = self.expand_next_cluster(context, &mut expansion) {
+            clusters.push(cluster);
+        }
+        self.add_unassigned_to_noise(&mut expansion);
+        let mut noise_cluster = expansion.noise_cluster;
+        if self.min_cluster_size > 1 {
+            let (kept, removed): (Vec<_>, Vec<_>) = clusters
+                .into_iter()
+                .partition(|cluster| self.is_large_enough(cluster));
+            clusters = kept;
+            let mut clustered = vec![false; context.points.len()];
+            for assignment in clusters.iter().flatten() {
+                clustered[assignment.index] = true;
             }
-            visited[point_index] = true;
-            let neighbor_indices = self.region_query(points, point_index);
-            let point_label = self.mu_min_p(self.density(point_index, &neighbor_indices, points));
-            if point_label == 0.0 {
-                noise_cluster.push(Assignment {
-                    index: point_index,
-                    category: Category::Noise,
-                    label: 1.0,
-                });
-            } else {
-                clusters.push(self.expand_cluster_fuzzy(
-                    point_label,
-                    point_index,
-                    neighbor_indices,
-                    points,
-                    &mut visited,
-                ));
+            add_to_noise(&mut noise_cluster, clustered, removed.into_iter().flatten());
+        }
+        // Number clusters only now, as small ones may have been removed.
+        for (cluster_id, cluster) in clusters.iter_mut().enumerate() {
+            for assignment in cluster {
+                assignment.cluster_id = Some(cluster_id);
             }
         }
         if !noise_cluster.is_empty() {
@@ -174,101 +1328,356 @@ impl FuzzyDBSCAN {
         clusters
     }
 
-    fn expand_cluster_fuzzy<P: MetricSpace>(
+    /// Returns the initial state of expanding clusters from all points.
+    fn expansion<'a, I, P>(
+        &self,
+        context: &Context<P, S, I>,
+        progress: Option<&'a dyn Fn(ProgressReport)>,
+    ) -> Expansion<'a, S> {
+        let points = context.points;
+        let mut seeds: Vec<usize> = (0..points.len()).collect();
+        if self.expansion_order == ExpansionOrder::HighestDensity {
+            let label = |point_index: usize| context.neighborhoods[point_index].1;
+            seeds.sort_by(|&a, &b| label(b).partial_cmp(&label(a)).unwrap_or(Ordering::Equal));
+        }
+        Expansion {
+            seeds: seeds.into_iter(),
+            visited: vec![false; points.len()],
+            assigned: vec![false; points.len()],
+            noise_cluster: Vec::new(),
+            scratch: Scratch {
+                queued: vec![false; points.len()],
+                core_labels: vec![S::zero(); points.len()],
+                non_core_neighbors: BTreeMap::new(),
+                visited_count: 0,
+                clusters_found: 0,
+                progress,
+            },
+        }
+    }
+
+    /// Visits seeds until one is a core point and returns its cluster, whose assignments have no
+    /// identifiers yet. Seeds that turn out to be noise are collected in the expansion.
+    fn expand_next_cluster<I: SpatialIndex<P, S>, P: MetricSpace<S>>(
+        &self,
+        context: &Context<P, S, I>,
+        expansion: &mut Expansion<S>,
+    ) -> Option<Cluster<S>> {
+        for point_index in expansion.seeds.by_ref() {
+            if expansion.visited[point_index] {
+                continue;
+            }
+            let scratch = &mut expansion.scratch;
+            self.visit(point_index, &mut expansion.visited, scratch);
+            let core = self.queried_core(point_index, context, &mut scratch.non_core_neighbors);
+            if let Some((neighbors, point_label)) = core {
+                scratch.clusters_found += 1;
+                let cluster = self.expand_cluster_fuzzy(
+                    point_label,
+                    point_index,
+                    neighbors,
+                    context,
+                    &mut expansion.visited,
+                    scratch,
+                );
+                for assignment in &cluster {
+                    expansion.assigned[assignment.index] = true;
+                }
+                return Some(cluster);
+            }
+            expansion.assigned[point_index] = true;
+            expansion.noise_cluster.push(Assignment {
+                index: point_index,
+                cluster_id: None,
+                category: Category::Noise,
+                label: S::one(),
+            });
+        }
+        None
+    }
+
+    /// Adds all points that are in neither a cluster nor the noise cluster to the latter, i.e.,
+    /// border points below the `border_threshold` in all of their clusters.
+    fn add_unassigned_to_noise(&self, expansion: &mut Expansion<S>) {
+        if self.border_threshold <= S::zero() {
+            return;
+        }
+        for (point_index, assigned) in expansion.assigned.iter_mut().enumerate() {
+            if !*assigned {
+                *assigned = true;
+                expansion.noise_cluster.push(Assignment {
+                    index: point_index,
+                    cluster_id: None,
+                    category: Category::Noise,
+                    label: S::one(),
+                });
+            }
+        }
+    }
+
+    /// Returns whether a point with the core label `label` is a core point.
+    fn is_core_label(&self, label: S) -> bool {
+        label > self.core_threshold
+    }
+
+    /// Returns whether the `cluster` has at least `min_cluster_size` distinct points.
+    fn is_large_enough(&self, cluster: &Cluster<S>) -> bool {
+        let indices: BTreeSet<usize> = cluster.iter().map(|a| a.index).collect();
+        indices.len() >= self.min_cluster_size
+    }
+
+    fn expand_cluster_fuzzy<I: SpatialIndex<P, S>, P: MetricSpace<S>>(
         &self,
-        point_label: f64,
+        point_label: S,
         point_index: usize,
-        mut neighbor_indices: HashSet<usize>,
-        points: &[P],
+        neighbors: Cow<[Neighbor<S>]>,
+        context: &Context<P, S, I>,
         visited: &mut [bool],
-    ) -> Vec<Assignment> {
+        scratch: &mut Scratch<S>,
+    ) -> Vec<Assignment<S>> {
         let mut cluster = vec![Assignment {
             index: point_index,
+            cluster_id: None,
             category: Category::Core,
             label: point_label,
         }];
+        scratch.queued[point_index] = true;
+        scratch.core_labels[point_index] = point_label;
         let mut border_points = Vec::new();
-        let mut neighbor_visited = vec![false; points.len()];
-        while let Some(neighbor_index) = take_arbitrary(&mut neighbor_indices) {
-            neighbor_visited[neighbor_index] = true;
-            visited[neighbor_index] = true;
-            let neighbor_neighbor_indices = self.region_query(points, neighbor_index);
-            let neighbor_label =
-                self.mu_min_p(self.density(neighbor_index, &neighbor_neighbor_indices, points));
-            if neighbor_label > 0.0 {
-                for neighbor_neighbor_index in neighbor_neighbor_indices {
-                    if !neighbor_visited[neighbor_neighbor_index] {
-                        neighbor_indices.insert(neighbor_neighbor_index);
+        let mut neighbor_indices = ExpansionQueue::new(self.expansion_order);
+        for &(neighbor_index, _, _) in neighbors.iter() {
+            scratch.queued[neighbor_index] = true;
+            neighbor_indices.push(neighbor_index, &context.neighborhoods);
+        }
+        while let Some(neighbor_index) = neighbor_indices.pop() {
+            self.visit(neighbor_index, visited, scratch);
+            let core = self.queried_core(neighbor_index, context, &mut scratch.non_core_neighbors);
+            if let Some((neighbor_neighbors, neighbor_label)) = core {
+                for &(neighbor_neighbor_index, _, _) in neighbor_neighbors.iter() {
+                    if !scratch.queued[neighbor_neighbor_index] {
+                        scratch.queued[neighbor_neighbor_index] = true;
+                        neighbor_indices.push(neighbor_neighbor_index, &context.neighborhoods);
                     }
                 }
                 cluster.push(Assignment {
                     index: neighbor_index,
+                    cluster_id: None,
                     category: Category::Core,
                     label: neighbor_label,
                 });
+                scratch.core_labels[neighbor_index] = neighbor_label;
             } else {
-                border_points.push(Assignment {
-                    index: neighbor_index,
-                    category: Category::Border,
-                    label: f64::MAX,
-                });
+                border_points.push(neighbor_index);
             }
         }
-        for border_point in &mut border_points {
-            for cluster_point in &cluster {
-                let mu_distance =
-                    self.mu_distance(&points[border_point.index], &points[cluster_point.index]);
-                if mu_distance > 0.0 {
-                    border_point.label =
-                        cluster_point.label.min(mu_distance).min(border_point.label);
+        // Only cores within eps_max affect the label, which are all neighbors of the border point.
+        for border_index in border_points {
+            let neighbors = match context.neighborhoods.get(border_index) {
+                Some((neighbors, _)) => neighbors,
+                None => &scratch.non_core_neighbors[&border_index],
+            };
+            let mut label = None;
+            for &(neighbor_index, _, mu_distance) in neighbors.iter() {
+                let core_label = scratch.core_labels[neighbor_index];
+                if core_label > S::zero() && mu_distance > S::zero() {
+                    let contribution = core_label.min(mu_distance);
+                    label = Some(match (label, self.border_label_mode) {
+                        (None, _) => contribution,
+                        (Some(label), BorderLabelMode::MinCore) => contribution.min(label),
+                        (Some(label), BorderLabelMode::MaxCore) => contribution.max(label),
+                    });
                 }
             }
+            // A border point exactly eps_max away from all of its cores has no contribution.
+            let label = label.unwrap_or_else(S::zero);
+            if label < self.border_threshold {
+                scratch.queued[border_index] = false;
+                continue;
+            }
+            cluster.push(Assignment {
+                index: border_index,
+                cluster_id: None,
+                category: Category::Border,
+                label,
+            });
+        }
+        // Every other queued point ended up in the cluster, thus only reset those.
+        for assignment in &cluster {
+            scratch.queued[assignment.index] = false;
+            scratch.core_labels[assignment.index] = S::zero();
         }
-        cluster.append(&mut border_points);
         cluster
     }
 
-    fn region_query<P: MetricSpace>(&self, points: &[P], point_index: usize) -> HashSet<usize> {
-        points
-            .iter()
-            .enumerate()
-            .filter(|(neighbor_index, neighbor_point)| {
-                *neighbor_index != point_index
-                    && neighbor_point.distance(&points[point_index]) <= self.eps_max
+    /// Marks the point at `point_index` as visited and reports the progress every
+    /// `progress_interval` points and once all points have been visited.
+    fn visit(&self, point_index: usize, visited: &mut [bool], scratch: &mut Scratch<S>) {
+        if visited[point_index] {
+            return;
+        }
+        visited[point_index] = true;
+        scratch.visited_count += 1;
+        if let Some(progress) = scratch.progress {
+            let total_points = visited.len();
+            let interval_reached =
+                self.progress_interval > 0 && scratch.visited_count % self.progress_interval == 0;
+            if interval_reached || scratch.visited_count == total_points {
+                progress(ProgressReport {
+                    points_visited: scratch.visited_count,
+                    total_points,
+                    clusters_found: scratch.clusters_found,
+                });
+            }
+        }
+    }
+
+    /// Returns the neighbors and core label of the point at `point_index`, which are looked up if
+    /// precomputed.
+    fn neighborhood<'c, I: SpatialIndex<P, S>, P: MetricSpace<S>>(
+        &self,
+        point_index: usize,
+        context: &'c Context<P, S, I>,
+    ) -> (Cow<'c, [Neighbor<S>]>, S) {
+        if let Some((neighbors, label)) = context.neighborhoods.get(point_index) {
+            return (Cow::Borrowed(neighbors), *label);
+        }
+        let neighbors = self.fuzzy_region_query(point_index, context);
+        let label = self.mu_min_p(self.density(&neighbors, context));
+        (Cow::Owned(neighbors), label)
+    }
+
+    /// Returns the [neighborhood](Self::neighborhood) of the point at `point_index` if it is a
+    /// core point, such that every point is queried only once: core points are expanded in exactly
+    /// one cluster, whereas the neighbors of non-core points are kept in `non_core_neighbors`
+    /// unless precomputed.
+    fn queried_core<'c, I: SpatialIndex<P, S>, P: MetricSpace<S>>(
+        &self,
+        point_index: usize,
+        context: &'c Context<P, S, I>,
+        non_core_neighbors: &mut BTreeMap<usize, Vec<Neighbor<S>>>,
+    ) -> Option<(Cow<'c, [Neighbor<S>]>, S)> {
+        if non_core_neighbors.contains_key(&point_index) {
+            return None;
+        }
+        let (neighbors, label) = self.neighborhood(point_index, context);
+        if self.is_core_label(label) {
+            return Some((neighbors, label));
+        }
+        if let Cow::Owned(neighbors) = neighbors {
+            non_core_neighbors.insert(point_index, neighbors);
+        }
+        None
+    }
+
+    /// Returns the neighbors and densities of all points, which are computed in parallel if the
+    /// `rayon` feature is enabled.
+    fn precompute_neighborhoods<I: SpatialIndex<P, S>, P: MetricSpace<S>>(
+        &self,
+        context: &Context<P, S, I>,
+    ) -> Vec<(Vec<Neighbor<S>>, S)> {
+        #[cfg(not(feature = "rayon"))]
+        let point_indices = 0..context.points.len();
+        #[cfg(feature = "rayon")]
+        let point_indices = (0..context.points.len()).into_par_iter();
+        point_indices
+            .map(|point_index| {
+                let neighbors = self.fuzzy_region_query(point_index, context);
+                let density = self.density(&neighbors, context);
+                (neighbors, density)
             })
-            .map(|(neighbor_index, _)| neighbor_index)
-            .collect() //TODO: would be neat to prevent this allocation.
+            .collect()
     }
 
-    fn density<P: MetricSpace>(
+    /// Returns the [neighbors](Self::region_query_with_distances) of the point at `point_index`.
+    fn fuzzy_region_query<I: SpatialIndex<P, S>, P: MetricSpace<S>>(
         &self,
         point_index: usize,
-        neighbor_indices: &HashSet<usize>,
-        points: &[P],
-    ) -> f64 {
-        1.0 + neighbor_indices.iter().fold(0.0, |sum, &neighbor_index| {
-            sum + self.mu_distance(&points[point_index], &points[neighbor_index])
-        })
+        context: &Context<P, S, I>,
+    ) -> Vec<Neighbor<S>> {
+        context
+            .index
+            .region_query_with_distances(context.points, point_index)
+            .into_iter()
+            .map(|(neighbor_index, distance)| {
+                (neighbor_index, distance, self.mu_of_distance(distance))
+            })
+            .collect()
     }
 
-    fn mu_min_p(&self, n: f64) -> f64 {
-        if n >= self.pts_max {
-            1.0
-        } else if n < self.pts_min {
-            0.0
+    fn density<I, P>(&self, neighbors: &[Neighbor<S>], context: &Context<P, S, I>) -> S {
+        let weights = context.weights;
+        let contribution = |&(neighbor_index, distance, mu_distance): &Neighbor<S>| {
+            let membership = self.density_membership(distance, mu_distance);
+            match weights {
+                Some(weights) => membership * weights[neighbor_index],
+                None => membership,
+            }
+        };
+        // Parallel sums are not reproducible, thus only sum sequentially if deterministic.
+        #[cfg(any(not(feature = "rayon"), feature = "deterministic"))]
+        let sum = neighbors
+            .iter()
+            .fold(S::zero(), |sum, neighbor| sum + contribution(neighbor));
+        #[cfg(all(feature = "rayon", not(feature = "deterministic")))]
+        let sum = neighbors
+            .par_iter()
+            .map(contribution)
+            .reduce(S::zero, |a, b| a + b);
+        self.self_density() + sum
+    }
+
+    /// Returns the contribution of a point to its own density.
+    fn self_density(&self) -> S {
+        if self.self_included_in_density {
+            S::one()
         } else {
-            (n - self.pts_min) / (self.pts_max - self.pts_min)
+            S::zero()
         }
     }
 
-    fn mu_distance<P: MetricSpace>(&self, a: &P, b: &P) -> f64 {
-        let distance = a.distance(b);
-        if distance <= self.eps_min {
-            1.0
-        } else if distance > self.eps_max {
-            0.0
-        } else {
-            (self.eps_max - distance) / (self.eps_max - self.eps_min)
+    /// Returns how much a neighbor at `distance` with the distance membership `mu_distance`
+    /// contributes to the density, depending on the density mode.
+    fn density_membership(&self, distance: S, mu_distance: S) -> S {
+        match self.density_mode {
+            DensityMode::FuzzyWeighted => mu_distance,
+            DensityMode::CrispInner if distance <= self.eps_min => S::one(),
+            DensityMode::CrispInner => S::zero(),
+        }
+    }
+
+    fn mu_min_p(&self, n: S) -> S {
+        self.core_membership.eval(n, self.pts_min, self.pts_max)
+    }
+
+    fn mu_distance<P: MetricSpace<S>>(&self, a: &P, b: &P) -> S {
+        self.mu_of_distance(a.distance(b))
+    }
+
+    fn mu_of_distance(&self, distance: S) -> S {
+        self.border_membership
+            .eval(-distance, -self.eps_max, -self.eps_min)
+    }
+}
+
+/// Adds all points of `removed` clusters to the `noise_cluster`, unless they are already noise or
+/// `clustered`, i.e., border points of a kept cluster.
+fn add_to_noise<S: Float>(
+    noise_cluster: &mut Cluster<S>,
+    mut clustered: Vec<bool>,
+    removed: impl IntoIterator<Item = Assignment<S>>,
+) {
+    for assignment in noise_cluster.iter() {
+        clustered[assignment.index] = true;
+    }
+    for assignment in removed {
+        if !clustered[assignment.index] {
+            clustered[assignment.index] = true;
+            noise_cluster.push(Assignment {
+                index: assignment.index,
+                cluster_id: None,
+                category: Category::Noise,
+                label: S::one(),
+            });
         }
     }
 }
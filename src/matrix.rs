@@ -0,0 +1,72 @@
+//! Precomputed pairwise distances.
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Index;
+use num_traits::Float;
+
+use crate::MetricSpace;
+
+/// A symmetric matrix of pairwise distances, stored in row-major order.
+///
+/// Indexing with `matrix[i][j]` returns the distance between the points `i` and `j`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix<S = f64> {
+    size: usize,
+    distances: Vec<S>,
+}
+
+impl<S: Float> DistanceMatrix<S> {
+    /// Creates a matrix of `size × size` row-major `distances`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of `distances` is not `size * size`.
+    pub fn new(size: usize, distances: Vec<S>) -> Self {
+        assert_eq!(distances.len(), size * size, "matrix is not square");
+        DistanceMatrix { size, distances }
+    }
+
+    /// Creates a matrix by computing the `distance` between all pairs of `points`.
+    pub fn from_points<P, F: Fn(&P, &P) -> S>(points: &[P], distance: F) -> Self {
+        let size = points.len();
+        let mut distances = vec![S::zero(); size * size];
+        for i in 0..size {
+            for j in (i + 1)..size {
+                let d = distance(&points[i], &points[j]);
+                distances[i * size + j] = d;
+                distances[j * size + i] = d;
+            }
+        }
+        DistanceMatrix { size, distances }
+    }
+
+    /// Returns the number of points.
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns `true` if the matrix contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+}
+
+impl<S> Index<usize> for DistanceMatrix<S> {
+    type Output = [S];
+
+    fn index(&self, row: usize) -> &[S] {
+        &self.distances[row * self.size..(row + 1) * self.size]
+    }
+}
+
+/// A point that looks up its distances in a matrix.
+pub(crate) struct MatrixPoint<'a, S> {
+    pub(crate) matrix: &'a DistanceMatrix<S>,
+    pub(crate) index: usize,
+}
+
+impl<S: Float + Sync> MetricSpace<S> for MatrixPoint<'_, S> {
+    fn distance(&self, other: &Self) -> S {
+        self.matrix[self.index][other.index]
+    }
+}
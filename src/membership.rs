@@ -0,0 +1,77 @@
+//! Membership functions to turn densities and distances into soft labels.
+//!
+//! All functions must return `0.0` for `x < lo` and `1.0` for `x >= hi`, such that clustering
+//! reduces to classic DBSCAN if `lo = hi`. They only differ in the transition in between.
+use alloc::boxed::Box;
+use core::fmt;
+use num_traits::Float;
+
+/// A trait to compute the degree of membership of a value.
+pub trait MembershipFn<S = f64>: fmt::Debug + Send + Sync {
+    /// Returns the degree of membership of `x` in the fuzzy set rising from `lo` to `hi`.
+    fn eval(&self, x: S, lo: S, hi: S) -> S;
+}
+
+/// A linear transition, i.e., a trapezoidal membership function.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LinearMembership;
+
+impl<S: Float> MembershipFn<S> for LinearMembership {
+    fn eval(&self, x: S, lo: S, hi: S) -> S {
+        if x >= hi {
+            S::one()
+        } else if x < lo {
+            S::zero()
+        } else {
+            (x - lo) / (hi - lo)
+        }
+    }
+}
+
+/// A Gaussian transition centered at `hi`, whose standard deviation is `sigma_factor` times the
+/// width of the transition.
+#[derive(Debug, Clone, Copy)]
+pub struct GaussianMembership<S = f64> {
+    /// The standard deviation relative to `hi - lo`.
+    pub sigma_factor: S,
+}
+
+impl<S: Float + Send + Sync + fmt::Debug> MembershipFn<S> for GaussianMembership<S> {
+    fn eval(&self, x: S, lo: S, hi: S) -> S {
+        if x >= hi {
+            S::one()
+        } else if x < lo {
+            S::zero()
+        } else {
+            let sigma = self.sigma_factor * (hi - lo);
+            let two = S::one() + S::one();
+            (-(hi - x).powi(2) / (two * sigma * sigma)).exp()
+        }
+    }
+}
+
+/// A sigmoidal transition centered between `lo` and `hi`, whose slope is `steepness` relative
+/// to the width of the transition.
+#[derive(Debug, Clone, Copy)]
+pub struct SigmoidMembership<S = f64> {
+    /// The steepness relative to `hi - lo`.
+    pub steepness: S,
+}
+
+impl<S: Float + Send + Sync + fmt::Debug> MembershipFn<S> for SigmoidMembership<S> {
+    fn eval(&self, x: S, lo: S, hi: S) -> S {
+        if x >= hi {
+            S::one()
+        } else if x < lo {
+            S::zero()
+        } else {
+            let two = S::one() + S::one();
+            let center = (lo + hi) / two;
+            S::one() / (S::one() + (-self.steepness * (x - center) / (hi - lo)).exp())
+        }
+    }
+}
+
+pub(crate) fn default_membership<S: Float>() -> Box<dyn MembershipFn<S>> {
+    Box::new(LinearMembership)
+}
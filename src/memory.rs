@@ -0,0 +1,51 @@
+//! Estimation of memory usage.
+use alloc::vec::Vec;
+use core::mem::size_of;
+use num_traits::Float;
+
+use crate::{Assignment, FuzzyDBSCAN, Neighbor};
+
+/// The estimated memory usage of clustering, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryEstimate {
+    /// The flags and labels kept for every point, e.g., whether it has been visited.
+    pub visited_bytes: usize,
+    /// The neighbors of all points, including the expansion queue.
+    pub neighbor_sets_bytes: usize,
+    /// The returned clusters.
+    pub cluster_output_bytes: usize,
+    /// The sum of all other estimates.
+    pub total_bytes: usize,
+}
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Estimates the memory usage of [clustering](Self::cluster) `n_points` points with
+    /// `average_neighbors` neighbors within `eps_max` each, e.g., as found by a
+    /// [dry run](Self::dry_run) on a sample.
+    ///
+    /// The estimate is an upper bound of the buffers used by the algorithm, which assumes that
+    /// the neighbors of all points are kept at once, as when expanding the densest points first
+    /// or with the `rayon` feature. Otherwise, only the neighbors of non-core points are kept and
+    /// actual usage may be much lower. Border points of several clusters add one assignment per
+    /// additional cluster, and the points themselves are not included.
+    pub fn estimate_memory_usage(
+        &self,
+        n_points: usize,
+        average_neighbors: usize,
+    ) -> MemoryEstimate {
+        // The visited and queued flags and the core labels.
+        let visited_bytes = n_points * (2 * size_of::<bool>() + size_of::<S>());
+        // Each neighborhood with its label, and the point in the expansion queue.
+        let neighborhood_bytes = size_of::<(Vec<Neighbor<S>>, S)>()
+            + average_neighbors * size_of::<Neighbor<S>>()
+            + size_of::<usize>();
+        let neighbor_sets_bytes = n_points * neighborhood_bytes;
+        let cluster_output_bytes = n_points * size_of::<Assignment<S>>();
+        MemoryEstimate {
+            visited_bytes,
+            neighbor_sets_bytes,
+            cluster_output_bytes,
+            total_bytes: visited_bytes + neighbor_sets_bytes + cluster_output_bytes,
+        }
+    }
+}
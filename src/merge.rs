@@ -0,0 +1,152 @@
+//! Merging of clusters.
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::{Assignment, Category, Cluster};
+
+/// Computes the Jaccard similarity of the border points of each pair of `clusters`, i.e., the
+/// number of shared border points divided by the number of border points in either cluster.
+///
+/// Entries on the diagonal are `1`, and entries of clusters without border points are `0`
+/// otherwise.
+pub fn cluster_overlap_matrix<S: Float>(clusters: &[Cluster<S>]) -> Vec<Vec<S>> {
+    let borders = border_sets(clusters);
+    (0..clusters.len())
+        .map(|a| {
+            (0..clusters.len())
+                .map(|b| {
+                    let union = borders[a].union(&borders[b]).count();
+                    if a == b {
+                        S::one()
+                    } else if union == 0 {
+                        S::zero()
+                    } else {
+                        let shared = borders[a].intersection(&borders[b]).count();
+                        S::from(shared).unwrap() / S::from(union).unwrap()
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Merges all clusters that share more than `overlap_threshold` of their border points, i.e.,
+/// whose shared border points exceed that fraction of the smaller border.
+///
+/// Merging is transitive and a merged cluster takes the position of its first cluster. Each point
+/// is kept once per cluster with its highest label, and is a core point if it is a core point in
+/// any of the merged clusters. The noise cluster is kept as is and cluster identifiers are renumbered.
+pub fn merge_overlapping_clusters<S: Float>(
+    clusters: Vec<Cluster<S>>,
+    overlap_threshold: S,
+) -> Vec<Cluster<S>> {
+    let (mut clusters, noise): (Vec<_>, Vec<_>) = clusters
+        .into_iter()
+        .partition(|cluster| cluster.iter().any(|a| a.category != Category::Noise));
+    let borders = border_sets(&clusters);
+    // Each cluster points to a cluster with a lower index that it is merged into.
+    let mut parents: Vec<usize> = (0..clusters.len()).collect();
+    let find = |parents: &[usize], mut i: usize| {
+        while parents[i] != i {
+            i = parents[i];
+        }
+        i
+    };
+    for a in 0..clusters.len() {
+        for b in a + 1..clusters.len() {
+            let smaller = borders[a].len().min(borders[b].len());
+            if smaller == 0 {
+                continue;
+            }
+            let shared = borders[a].intersection(&borders[b]).count();
+            let overlap = S::from(shared).unwrap() / S::from(smaller).unwrap();
+            if overlap > overlap_threshold {
+                let (root_a, root_b) = (find(&parents, a), find(&parents, b));
+                parents[root_a.max(root_b)] = root_a.min(root_b);
+            }
+        }
+    }
+    let mut merged: Vec<Cluster<S>> = Vec::new();
+    // The positions of the points in each merged cluster.
+    let mut point_positions: Vec<BTreeMap<usize, usize>> = Vec::new();
+    let mut positions = Vec::with_capacity(clusters.len());
+    for (i, cluster) in clusters.iter_mut().enumerate() {
+        let root = find(&parents, i);
+        let position = if root == i {
+            merged.push(Vec::new());
+            point_positions.push(BTreeMap::new());
+            merged.len() - 1
+        } else {
+            positions[root]
+        };
+        positions.push(position);
+        for assignment in cluster.drain(..) {
+            combine(
+                &mut merged[position],
+                &mut point_positions[position],
+                assignment,
+            );
+        }
+    }
+    for (cluster_id, cluster) in merged.iter_mut().enumerate() {
+        for assignment in cluster {
+            assignment.cluster_id = Some(cluster_id);
+        }
+    }
+    merged.extend(noise);
+    merged
+}
+
+/// Merges the clusters `a` and `b`, keeping each point once with its highest label and category,
+/// where `Core` takes precedence over `Border` and `Border` over `Noise`.
+///
+/// The merged cluster takes the identifier of `a`, or of `b` if `a` is empty.
+pub fn merge_clusters<S: Float>(a: Cluster<S>, b: Cluster<S>) -> Cluster<S> {
+    let cluster_id = a.first().or_else(|| b.first()).and_then(|a| a.cluster_id);
+    let mut merged = Vec::with_capacity(a.len() + b.len());
+    let mut point_positions = BTreeMap::new();
+    for assignment in a.into_iter().chain(b) {
+        combine(&mut merged, &mut point_positions, assignment);
+    }
+    for assignment in &mut merged {
+        assignment.cluster_id = cluster_id;
+    }
+    merged
+}
+
+/// Adds an `assignment` to a `cluster`, combining it with an existing assignment of the same
+/// point by the highest label and category.
+fn combine<S: Float>(
+    cluster: &mut Cluster<S>,
+    point_positions: &mut BTreeMap<usize, usize>,
+    assignment: Assignment<S>,
+) {
+    match point_positions.get(&assignment.index) {
+        Some(&position) => {
+            let existing = &mut cluster[position];
+            existing.label = existing.label.max(assignment.label);
+            if assignment.category.precedence() > existing.category.precedence() {
+                existing.category = assignment.category;
+            }
+        }
+        None => {
+            point_positions.insert(assignment.index, cluster.len());
+            cluster.push(assignment);
+        }
+    }
+}
+
+/// Returns the indices of the border points of each cluster.
+fn border_sets<S>(clusters: &[Cluster<S>]) -> Vec<BTreeSet<usize>> {
+    clusters
+        .iter()
+        .map(|cluster| {
+            cluster
+                .iter()
+                .filter(|a| a.category == Category::Border)
+                .map(|a| a.index)
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,421 @@
+//! Metrics to assess the quality of clustering results.
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use num_traits::Float;
+
+use crate::{defuzzify, Category, Cluster, FuzzyDBSCAN, FuzzyDBSCANError, MetricSpace};
+
+/// Computes the mean silhouette coefficient of all non-noise points.
+///
+/// Each point is attributed to the cluster in which it has the highest label. The silhouette of a
+/// point is `(b - a) / max(a, b)`, where `a` is the mean distance to the other points of its
+/// cluster and `b` is the mean distance to the points of the nearest other cluster. Points in
+/// single-point clusters, and all points if there is only one cluster, have a silhouette of `0`.
+/// Returns NaN if all points are noise.
+pub fn silhouette_score<S: Float, P: MetricSpace<S>>(points: &[P], clusters: &[Cluster<S>]) -> S {
+    let assignments = defuzzify(points.len(), clusters);
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); clusters.len()];
+    for (point_index, assignment) in assignments.iter().enumerate() {
+        if let Some(cluster_index) = assignment {
+            members[*cluster_index].push(point_index);
+        }
+    }
+    let mean_distance = |point_index: usize, others: &[usize]| {
+        let sum = others.iter().fold(S::zero(), |sum, &other_index| {
+            sum + points[point_index].distance(&points[other_index])
+        });
+        sum / S::from(others.len()).unwrap()
+    };
+    let mut sum = S::zero();
+    let mut count = 0;
+    for (cluster_index, cluster_members) in members.iter().enumerate() {
+        for &point_index in cluster_members {
+            count += 1;
+            if cluster_members.len() < 2 {
+                continue;
+            }
+            // The distance to the point itself is zero, thus only the divisor needs a fix.
+            let a = mean_distance(point_index, cluster_members)
+                * S::from(cluster_members.len()).unwrap()
+                / S::from(cluster_members.len() - 1).unwrap();
+            let b = members
+                .iter()
+                .enumerate()
+                .filter(|(other_index, others)| *other_index != cluster_index && !others.is_empty())
+                .map(|(_, others)| mean_distance(point_index, others))
+                .fold(S::infinity(), S::min);
+            if b.is_finite() {
+                sum = sum + (b - a) / a.max(b);
+            }
+        }
+    }
+    if count == 0 {
+        S::nan()
+    } else {
+        sum / S::from(count).unwrap()
+    }
+}
+
+/// Computes the Davies-Bouldin index of all clusters with core points, lower is better.
+///
+/// Since points only define distances, the centroid of each cluster is approximated by its
+/// medoid, i.e., the member with the least total distance to all other members. Returns NaN if
+/// there are less than two clusters.
+pub fn davies_bouldin_index<S: Float, P: MetricSpace<S>>(
+    points: &[P],
+    clusters: &[Cluster<S>],
+) -> S {
+    let scatters: Vec<(usize, S)> = clusters
+        .iter()
+        .filter(|cluster| cluster.iter().any(|a| a.category == Category::Core))
+        .map(|cluster| {
+            let mut members: Vec<usize> = cluster
+                .iter()
+                .filter(|a| a.category != Category::Noise)
+                .map(|a| a.index)
+                .collect();
+            members.sort_unstable();
+            members.dedup();
+            let total_distance = |point_index: usize| {
+                members.iter().fold(S::zero(), |sum, &other_index| {
+                    sum + points[point_index].distance(&points[other_index])
+                })
+            };
+            let (medoid, medoid_distance) = members
+                .iter()
+                .map(|&point_index| (point_index, total_distance(point_index)))
+                .fold((members[0], S::infinity()), |best, candidate| {
+                    if candidate.1 < best.1 {
+                        candidate
+                    } else {
+                        best
+                    }
+                });
+            (medoid, medoid_distance / S::from(members.len()).unwrap())
+        })
+        .collect();
+    if scatters.len() < 2 {
+        return S::nan();
+    }
+    let sum = scatters
+        .iter()
+        .enumerate()
+        .map(|(i, &(medoid_i, scatter_i))| {
+            scatters
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &(medoid_j, scatter_j))| {
+                    (scatter_i + scatter_j) / points[medoid_i].distance(&points[medoid_j])
+                })
+                .fold(S::neg_infinity(), S::max)
+        })
+        .fold(S::zero(), |sum, ratio| sum + ratio);
+    sum / S::from(scatters.len()).unwrap()
+}
+
+/// Computes the adjusted Rand index of two clusterings `a` and `b` of `n_points` points.
+///
+/// Each point is attributed to the cluster in which it has the highest label, all noise points
+/// are treated as one group. The index is `1` for identical clusterings and close to `0` for
+/// random ones. Returns NaN if there are less than two points.
+pub fn adjusted_rand_index<S: Float>(a: &[Cluster<S>], b: &[Cluster<S>], n_points: usize) -> S {
+    let assignments_a = defuzzify(n_points, a);
+    let assignments_b = defuzzify(n_points, b);
+    let (rows, columns) = (a.len() + 1, b.len() + 1);
+    let mut contingency = vec![0usize; rows * columns];
+    for (assignment_a, assignment_b) in assignments_a.iter().zip(assignments_b.iter()) {
+        let row = assignment_a.unwrap_or(a.len());
+        let column = assignment_b.unwrap_or(b.len());
+        contingency[row * columns + column] += 1;
+    }
+    let pairs = |count: usize| S::from(count * count.saturating_sub(1) / 2).unwrap();
+    let index = contingency
+        .iter()
+        .fold(S::zero(), |sum, &count| sum + pairs(count));
+    let sum_a = (0..rows).fold(S::zero(), |sum, row| {
+        sum + pairs(contingency[row * columns..(row + 1) * columns].iter().sum())
+    });
+    let sum_b = (0..columns).fold(S::zero(), |sum, column| {
+        sum + pairs(
+            (0..rows)
+                .map(|row| contingency[row * columns + column])
+                .sum(),
+        )
+    });
+    let expected_index = sum_a * sum_b / pairs(n_points);
+    let max_index = (sum_a + sum_b) / S::from(2).unwrap();
+    if max_index == expected_index {
+        S::one()
+    } else {
+        (index - expected_index) / (max_index - expected_index)
+    }
+}
+
+/// Computes the normalized mutual information of `predicted` clusters, e.g., as returned by
+/// [`defuzzify`], and `ground_truth` labels of the same points.
+///
+/// All noise points are treated as one group. The mutual information `H(Y) - H(Y|C)` of the
+/// ground truth `Y` and the clusters `C` is normalized by the arithmetic mean of `H(Y)` and
+/// `H(C)`, thus the result is `1` for identical partitions and `0` for independent ones.
+/// Returns NaN if there are no points.
+pub fn normalized_mutual_information<S: Float>(
+    predicted: &[Option<usize>],
+    ground_truth: &[usize],
+) -> S {
+    let mut joint: BTreeMap<(Option<usize>, usize), usize> = BTreeMap::new();
+    let mut clusters: BTreeMap<Option<usize>, usize> = BTreeMap::new();
+    let mut labels: BTreeMap<usize, usize> = BTreeMap::new();
+    for (&cluster, &label) in predicted.iter().zip(ground_truth) {
+        *joint.entry((cluster, label)).or_insert(0) += 1;
+        *clusters.entry(cluster).or_insert(0) += 1;
+        *labels.entry(label).or_insert(0) += 1;
+    }
+    let n = S::from(predicted.len().min(ground_truth.len())).unwrap();
+    let entropy = |counts: &mut dyn Iterator<Item = usize>| {
+        counts.fold(S::zero(), |sum, count| {
+            let p = S::from(count).unwrap() / n;
+            sum - p * p.ln()
+        })
+    };
+    let h_labels = entropy(&mut labels.values().copied());
+    let h_clusters = entropy(&mut clusters.values().copied());
+    let h_conditional = joint.iter().fold(S::zero(), |sum, ((cluster, _), &count)| {
+        let p = S::from(count).unwrap() / n;
+        sum - p * (S::from(count).unwrap() / S::from(clusters[cluster]).unwrap()).ln()
+    });
+    if n == S::zero() {
+        S::nan()
+    } else if h_labels + h_clusters == S::zero() {
+        S::one()
+    } else {
+        S::from(2).unwrap() * (h_labels - h_conditional) / (h_labels + h_clusters)
+    }
+}
+
+/// Computes the density-based clustering validation (DBCV) index of all clusters, which ranges
+/// from `-1` to `1`, higher is better.
+///
+/// Each point is attributed to the cluster in which it has the highest label. The core distance
+/// of a point is the distance to its `pts_min`-th nearest neighbor in the same cluster (or the
+/// farthest one in smaller clusters), which defines the mutual reachability distance
+/// `max(core(a), core(b), d(a, b))`. The sparseness of a cluster is the largest edge between
+/// internal nodes of the minimum spanning tree of its mutual reachability graph, the separation of
+/// two clusters is the smallest mutual reachability distance between their internal nodes. The
+/// index is the mean validity `(separation - sparseness) / max(separation, sparseness)` of all
+/// clusters weighted by their size, where noise points count as zero validity. Returns NaN if
+/// there are less than two clusters.
+///
+/// See Moulavi et al.: Density-Based Clustering Validation, SDM 2014.
+pub fn dbcv<S: Float, P: MetricSpace<S>>(
+    points: &[P],
+    clusters: &[Cluster<S>],
+    pts_min: usize,
+) -> S {
+    let assignments = defuzzify(points.len(), clusters);
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); clusters.len()];
+    for (point_index, assignment) in assignments.iter().enumerate() {
+        if let Some(cluster_index) = assignment {
+            members[*cluster_index].push(point_index);
+        }
+    }
+    members.retain(|cluster_members| !cluster_members.is_empty());
+    if members.len() < 2 {
+        return S::nan();
+    }
+    let mut core_distances = vec![S::zero(); points.len()];
+    for cluster_members in &members {
+        for &point_index in cluster_members {
+            let mut distances: Vec<S> = cluster_members
+                .iter()
+                .filter(|&&other_index| other_index != point_index)
+                .map(|&other_index| points[point_index].distance(&points[other_index]))
+                .collect();
+            distances.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            let k = pts_min.clamp(1, distances.len().max(1));
+            core_distances[point_index] = distances.get(k - 1).copied().unwrap_or_else(S::zero);
+        }
+    }
+    let mutual_reachability = |a: usize, b: usize| {
+        points[a]
+            .distance(&points[b])
+            .max(core_distances[a])
+            .max(core_distances[b])
+    };
+    // Prim's algorithm on the complete mutual reachability graph of every cluster.
+    let trees: Vec<(Vec<usize>, S)> = members
+        .iter()
+        .map(|cluster_members| {
+            let n = cluster_members.len();
+            let mut in_tree = vec![false; n];
+            let mut best = vec![(S::infinity(), 0); n];
+            let mut degrees = vec![0usize; n];
+            let mut edges = Vec::with_capacity(n.saturating_sub(1));
+            best[0].0 = S::zero();
+            for step in 0..n {
+                let next = (0..n)
+                    .filter(|&i| !in_tree[i])
+                    .min_by(|&i, &j| best[i].0.partial_cmp(&best[j].0).unwrap_or(Ordering::Equal))
+                    .unwrap();
+                in_tree[next] = true;
+                if step > 0 {
+                    let (weight, parent) = best[next];
+                    degrees[next] += 1;
+                    degrees[parent] += 1;
+                    edges.push((parent, next, weight));
+                }
+                for i in 0..n {
+                    if !in_tree[i] {
+                        let weight = mutual_reachability(cluster_members[next], cluster_members[i]);
+                        if weight < best[i].0 {
+                            best[i] = (weight, next);
+                        }
+                    }
+                }
+            }
+            // Leaves are excluded unless the tree has no internal nodes.
+            let internal = |i: usize| degrees[i] > 1 || n <= 2;
+            let sparseness = edges
+                .iter()
+                .filter(|&&(a, b, _)| internal(a) && internal(b))
+                .map(|&(_, _, weight)| weight)
+                .fold(S::zero(), S::max);
+            let internal_nodes = (0..n)
+                .filter(|&i| internal(i))
+                .map(|i| cluster_members[i])
+                .collect();
+            (internal_nodes, sparseness)
+        })
+        .collect();
+    let sum = trees
+        .iter()
+        .enumerate()
+        .map(|(i, (internal_i, sparseness))| {
+            let separation = trees
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .flat_map(|(_, (internal_j, _))| {
+                    internal_i.iter().flat_map(move |&a| {
+                        internal_j.iter().map(move |&b| mutual_reachability(a, b))
+                    })
+                })
+                .fold(S::infinity(), S::min);
+            let validity = (separation - *sparseness) / separation.max(*sparseness);
+            validity * S::from(members[i].len()).unwrap()
+        })
+        .fold(S::zero(), |sum, validity| sum + validity);
+    sum / S::from(points.len()).unwrap()
+}
+
+/// Computes how many of `n_eps_steps` evenly spaced values of `eps_max` (from `eps_min` up to
+/// `eps_max` of `fuzzy_dbscan`) each cluster survives after it appears before it merges with
+/// another one, higher is more robust, similar to the persistence in HDBSCAN.
+///
+/// Clusters are ordered by the value they first appear at, and then as returned by
+/// [`cluster`](FuzzyDBSCAN::cluster). Neighborhoods are
+/// [computed once](FuzzyDBSCAN::precompute_neighbors) and all other parameters are kept. Since
+/// increasing `eps_max` only adds core points and neighbors, clusters can only appear, grow, or
+/// merge, and clusters that never merge survive all remaining steps, e.g., `n_eps_steps - 1`
+/// steps for clusters found at the smallest value.
+pub fn cluster_persistence<S: Float + Send + Sync, P: MetricSpace<S>>(
+    points: &[P],
+    fuzzy_dbscan: &FuzzyDBSCAN<S>,
+    n_eps_steps: usize,
+) -> Result<Vec<S>, FuzzyDBSCANError> {
+    let neighbors = fuzzy_dbscan.precompute_neighbors(points)?;
+    if n_eps_steps == 0 {
+        return Ok(Vec::new());
+    }
+    let eps_max = |step: usize| {
+        let t = S::from(step).unwrap() / S::from(n_eps_steps.max(2) - 1).unwrap();
+        fuzzy_dbscan.eps_min + (fuzzy_dbscan.eps_max - fuzzy_dbscan.eps_min) * t
+    };
+    // Track every cluster by one of its core points, from the value it first appears at.
+    let mut cores: Vec<usize> = Vec::new();
+    let mut persistence = Vec::new();
+    let mut merged = Vec::new();
+    for step in 0..n_eps_steps {
+        let clusters = fuzzy_dbscan.recluster_with_eps_max(&neighbors, eps_max(step));
+        let mut core_clusters = vec![None; points.len()];
+        for (cluster_index, cluster) in clusters.iter().enumerate() {
+            for a in cluster.iter().filter(|a| a.category == Category::Core) {
+                core_clusters[a.index] = Some(cluster_index);
+            }
+        }
+        let tracked = cores.len();
+        for (cluster_index, cluster) in clusters.iter().enumerate() {
+            if cores[..tracked]
+                .iter()
+                .any(|&core| core_clusters[core] == Some(cluster_index))
+            {
+                continue;
+            }
+            if let Some(a) = cluster.iter().find(|a| a.category == Category::Core) {
+                cores.push(a.index);
+                persistence.push(0);
+                merged.push(false);
+            }
+        }
+        // Clusters that appear at this value contain no other tracked core point.
+        for i in 0..tracked {
+            let cluster = core_clusters[cores[i]];
+            merged[i] |= cluster.is_some()
+                && (0..tracked).any(|j| j != i && core_clusters[cores[j]] == cluster);
+            if !merged[i] {
+                persistence[i] += 1;
+            }
+        }
+    }
+    Ok(persistence
+        .into_iter()
+        .map(|steps| S::from(steps).unwrap())
+        .collect())
+}
+
+/// Computes the Shannon entropy (in nats) of the labels of the point at `point_index` across
+/// all clusters, normalized to sum to one.
+///
+/// The entropy is `0` for points in a single cluster, such as core points, and grows with the
+/// number of clusters and the uniformity of the labels, e.g., `ln(2)` for a border point with
+/// equal labels in two clusters. Noise assignments are ignored, so noise points and points without
+/// assignments have an entropy of `0`.
+pub fn assignment_entropy<S: Float>(point_index: usize, clusters: &[Cluster<S>]) -> S {
+    let labels: Vec<S> = clusters
+        .iter()
+        .flatten()
+        .filter(|a| a.index == point_index && a.category != Category::Noise)
+        .map(|a| a.label)
+        .collect();
+    label_entropy(&labels)
+}
+
+/// Computes the mean [assignment entropy](assignment_entropy) of those of `n_points` points that
+/// are assigned to any cluster, lower is crisper. Returns NaN if there are no such points.
+pub fn mean_entropy<S: Float>(clusters: &[Cluster<S>], n_points: usize) -> S {
+    let mut labels: Vec<Vec<S>> = vec![Vec::new(); n_points];
+    for assignment in clusters.iter().flatten() {
+        if assignment.category != Category::Noise {
+            labels[assignment.index].push(assignment.label);
+        }
+    }
+    let clustered: Vec<&Vec<S>> = labels.iter().filter(|labels| !labels.is_empty()).collect();
+    let sum = clustered
+        .iter()
+        .fold(S::zero(), |sum, labels| sum + label_entropy(labels));
+    sum / S::from(clustered.len()).unwrap()
+}
+
+/// Computes the entropy of `labels` after normalizing them to sum to one.
+fn label_entropy<S: Float>(labels: &[S]) -> S {
+    let total = labels.iter().fold(S::zero(), |sum, &label| sum + label);
+    labels
+        .iter()
+        .filter(|&&label| label > S::zero())
+        .fold(S::zero(), |entropy, &label| {
+            let p = label / total;
+            entropy - p * p.ln()
+        })
+}
@@ -0,0 +1,101 @@
+//! Continuous outlier scores.
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use num_traits::Float;
+
+use crate::index::{BruteForce, SpatialIndex};
+use crate::{query, Cluster, Context, FuzzyDBSCAN, FuzzyDBSCANError, MetricSpace};
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Computes an outlier score between `0.0` and `1.0` for each point of a clustering, in the
+    /// order of `points`.
+    ///
+    /// The score is one minus the highest distance membership of the point to any core point,
+    /// so core points score `0.0` and points farther than `eps_max` from all core points, such
+    /// as most noise points, score `1.0`.
+    pub fn outlier_scores<P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+        clusters: &[Cluster<S>],
+    ) -> Vec<S> {
+        let core_points = query::core_points(clusters);
+        points
+            .iter()
+            .map(|point| {
+                let mut score = S::one();
+                for &core_index in &core_points {
+                    score = score.min(S::one() - self.mu_distance(point, &points[core_index]));
+                    if score <= S::zero() {
+                        break;
+                    }
+                }
+                score
+            })
+            .collect()
+    }
+
+    /// Computes the local outlier factor of each point, in the order of `points`, which is the
+    /// mean density of its `k` nearest neighbors divided by its own density.
+    ///
+    /// Densities are the fuzzy densities used for clustering, i.e., the sums of the distance
+    /// memberships of all neighbors within `eps_max`. Points in regions of uniform density score
+    /// about `1.0`, whereas outliers score higher, e.g., above `1.5`. The nearest neighbors are
+    /// looked up within `eps_max` if there are at least `k` of them, otherwise among all points.
+    /// If `self_included_in_density` is `false`, points without neighbors within `eps_max` have
+    /// no density and score infinity, unless their nearest neighbors have none either.
+    ///
+    /// Returns an error if `k` is zero or not less than the number of points.
+    pub fn local_outlier_factor<P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+        k: usize,
+    ) -> Result<Vec<S>, FuzzyDBSCANError> {
+        self.validate()?;
+        if k == 0 || k >= points.len() {
+            return Err(FuzzyDBSCANError::InvalidNeighborCount);
+        }
+        let context = Context {
+            points,
+            weights: None,
+            index: BruteForce::build(points, self.eps_max),
+            neighborhoods: Vec::new(),
+        };
+        let neighborhoods = self.precompute_neighborhoods(&context);
+        let densities: Vec<S> = neighborhoods.iter().map(|(_, density)| *density).collect();
+        let scores = neighborhoods
+            .iter()
+            .enumerate()
+            .map(|(point_index, (neighbors, _))| {
+                let mut neighbors: Vec<(S, usize)> = if neighbors.len() >= k {
+                    neighbors
+                        .iter()
+                        .map(|&(neighbor_index, distance, _)| (distance, neighbor_index))
+                        .collect()
+                } else {
+                    (0..points.len())
+                        .filter(|&neighbor_index| neighbor_index != point_index)
+                        .map(|neighbor_index| {
+                            let distance = points[point_index].distance(&points[neighbor_index]);
+                            (distance, neighbor_index)
+                        })
+                        .collect()
+                };
+                neighbors.select_nth_unstable_by(k - 1, |a, b| {
+                    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+                });
+                let neighbor_density = neighbors[..k]
+                    .iter()
+                    .fold(S::zero(), |sum, &(_, neighbor_index)| {
+                        sum + densities[neighbor_index]
+                    })
+                    / S::from(k).unwrap();
+                if neighbor_density == densities[point_index] {
+                    S::one()
+                } else {
+                    neighbor_density / densities[point_index]
+                }
+            })
+            .collect();
+        Ok(scores)
+    }
+}
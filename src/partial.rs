@@ -0,0 +1,232 @@
+//! Reclustering with fixed assignments.
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::index::{BruteForce, SpatialIndex};
+use crate::{
+    Assignment, BorderLabelMode, Category, Cluster, Context, FuzzyDBSCAN, FuzzyDBSCANError,
+    MetricSpace,
+};
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Clusters a list of `points` while keeping the `fixed_assignments`, which are given along
+    /// with the index of their cluster (e.g., in a previous result), and only expands clusters
+    /// from the other points.
+    ///
+    /// Fixed points still contribute to the densities of the other points, but are not
+    /// re-examined. New core points connected to fixed core points join their clusters, which are
+    /// merged into the one with the lowest index, otherwise they form new clusters. New non-core
+    /// points become border points of all clusters with core points in their neighborhood, or
+    /// noise. Fixed
+    /// clusters keep their order, followed by the new clusters and the noise cluster (if any).
+    /// Unused cluster indices are skipped, thus fixed clusters keep their index only if the
+    /// indices are contiguous.
+    ///
+    /// Panics if a fixed assignment refers to a point outside of `points`.
+    pub fn partial_recluster<P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+        fixed_assignments: &[(usize, Assignment<S>)],
+    ) -> Result<Vec<Cluster<S>>, FuzzyDBSCANError> {
+        self.validate()?;
+        if points.is_empty() {
+            return Err(FuzzyDBSCANError::EmptyInput);
+        }
+        let context = Context {
+            points,
+            weights: None,
+            index: BruteForce::build(points, self.eps_max),
+            neighborhoods: Vec::new(),
+        };
+        let mut clusters: Vec<Cluster<S>> = Vec::new();
+        let mut noise_cluster = Vec::new();
+        let mut fixed = vec![false; points.len()];
+        // The cluster index and core label of every core point.
+        let mut cores: Vec<Option<(usize, S)>> = vec![None; points.len()];
+        for (cluster_index, assignment) in fixed_assignments {
+            fixed[assignment.index] = true;
+            if assignment.category == Category::Noise {
+                noise_cluster.push(Assignment {
+                    cluster_id: None,
+                    ..assignment.clone()
+                });
+                continue;
+            }
+            if clusters.len() <= *cluster_index {
+                clusters.resize_with(cluster_index + 1, Vec::new);
+            }
+            if assignment.category == Category::Core {
+                cores[assignment.index] = Some((*cluster_index, assignment.label));
+            }
+            clusters[*cluster_index].push(Assignment {
+                cluster_id: Some(*cluster_index),
+                ..assignment.clone()
+            });
+        }
+        // The cluster that each cluster has been merged into, or itself.
+        let mut roots: Vec<usize> = (0..clusters.len()).collect();
+        let neighborhoods: Vec<_> = (0..points.len())
+            .map(|point_index| {
+                (!fixed[point_index]).then(|| {
+                    let (neighbors, label) = self.neighborhood(point_index, &context);
+                    (neighbors.into_owned(), label)
+                })
+            })
+            .collect();
+        // Expand the new core points connected to each other, ignoring all fixed points.
+        let mut expanded = vec![false; points.len()];
+        for seed_index in 0..points.len() {
+            match &neighborhoods[seed_index] {
+                Some((_, label)) if self.is_core_label(*label) && !expanded[seed_index] => {}
+                _ => continue,
+            }
+            let mut members = vec![seed_index];
+            let mut queue = vec![seed_index];
+            let mut fixed_clusters = Vec::new();
+            expanded[seed_index] = true;
+            while let Some(point_index) = queue.pop() {
+                let (neighbors, _) = neighborhoods[point_index].as_ref().unwrap();
+                for &(neighbor_index, _, _) in neighbors {
+                    if fixed[neighbor_index] {
+                        if let Some((cluster_index, _)) = cores[neighbor_index] {
+                            fixed_clusters.push(cluster_index);
+                        }
+                        continue;
+                    }
+                    let is_core = matches!(
+                        &neighborhoods[neighbor_index],
+                        Some((_, label)) if self.is_core_label(*label)
+                    );
+                    if is_core && !expanded[neighbor_index] {
+                        expanded[neighbor_index] = true;
+                        members.push(neighbor_index);
+                        queue.push(neighbor_index);
+                    }
+                }
+            }
+            let mut fixed_roots: Vec<usize> = fixed_clusters
+                .into_iter()
+                .map(|cluster_index| find_root(&mut roots, cluster_index))
+                .collect();
+            fixed_roots.sort_unstable();
+            fixed_roots.dedup();
+            let cluster_index = match fixed_roots.first() {
+                Some(&cluster_index) => cluster_index,
+                None => {
+                    clusters.push(Vec::new());
+                    roots.push(clusters.len() - 1);
+                    clusters.len() - 1
+                }
+            };
+            for &fixed_root in &fixed_roots[fixed_roots.len().min(1)..] {
+                roots[fixed_root] = cluster_index;
+            }
+            for point_index in members {
+                let label = neighborhoods[point_index].as_ref().unwrap().1;
+                cores[point_index] = Some((cluster_index, label));
+                clusters[cluster_index].push(Assignment {
+                    index: point_index,
+                    cluster_id: Some(cluster_index),
+                    label,
+                    category: Category::Core,
+                });
+            }
+        }
+        // Merge the fixed clusters connected by new cores, which may share border points.
+        for cluster_index in 0..clusters.len() {
+            let root = find_root(&mut roots, cluster_index);
+            if root == cluster_index {
+                continue;
+            }
+            let mut positions: BTreeMap<usize, usize> = clusters[root]
+                .iter()
+                .enumerate()
+                .map(|(position, assignment)| (assignment.index, position))
+                .collect();
+            for assignment in core::mem::take(&mut clusters[cluster_index]) {
+                let Some(&position) = positions.get(&assignment.index) else {
+                    positions.insert(assignment.index, clusters[root].len());
+                    clusters[root].push(assignment);
+                    continue;
+                };
+                let existing = &mut clusters[root][position];
+                if assignment.category == Category::Core {
+                    *existing = assignment;
+                } else if existing.category == Category::Border {
+                    existing.label = match self.border_label_mode {
+                        BorderLabelMode::MinCore => existing.label.min(assignment.label),
+                        BorderLabelMode::MaxCore => existing.label.max(assignment.label),
+                    };
+                }
+            }
+        }
+        for (cluster_index, _) in cores.iter_mut().flatten() {
+            *cluster_index = find_root(&mut roots, *cluster_index);
+        }
+        // Only cores within eps_max affect the label, which are all neighbors of the border point.
+        for (point_index, neighborhood) in neighborhoods.iter().enumerate() {
+            let neighbors = match neighborhood {
+                Some((neighbors, label)) if !self.is_core_label(*label) => neighbors,
+                _ => continue,
+            };
+            let mut border_labels: BTreeMap<usize, Option<S>> = BTreeMap::new();
+            for &(neighbor_index, _, mu_distance) in neighbors {
+                if let Some((cluster_index, core_label)) = cores[neighbor_index] {
+                    let label = border_labels.entry(cluster_index).or_insert(None);
+                    if core_label > S::zero() && mu_distance > S::zero() {
+                        let contribution = core_label.min(mu_distance);
+                        *label = Some(match (*label, self.border_label_mode) {
+                            (None, _) => contribution,
+                            (Some(label), BorderLabelMode::MinCore) => contribution.min(label),
+                            (Some(label), BorderLabelMode::MaxCore) => contribution.max(label),
+                        });
+                    }
+                }
+            }
+            // A border point exactly eps_max away from all of its cores has no contribution.
+            let mut border_labels: BTreeMap<usize, S> = border_labels
+                .into_iter()
+                .map(|(cluster_index, label)| (cluster_index, label.unwrap_or_else(S::zero)))
+                .collect();
+            border_labels.retain(|_, label| *label >= self.border_threshold);
+            if border_labels.is_empty() {
+                noise_cluster.push(Assignment {
+                    index: point_index,
+                    cluster_id: None,
+                    label: S::one(),
+                    category: Category::Noise,
+                });
+            }
+            for (cluster_index, label) in border_labels {
+                clusters[cluster_index].push(Assignment {
+                    index: point_index,
+                    cluster_id: Some(cluster_index),
+                    label,
+                    category: Category::Border,
+                });
+            }
+        }
+        // Skip unused cluster indices of the fixed assignments, which would be empty otherwise.
+        clusters.retain(|cluster| !cluster.is_empty());
+        for (cluster_index, cluster) in clusters.iter_mut().enumerate() {
+            for assignment in cluster {
+                assignment.cluster_id = Some(cluster_index);
+            }
+        }
+        if !noise_cluster.is_empty() {
+            clusters.push(noise_cluster);
+        }
+        Ok(clusters)
+    }
+}
+
+/// Returns the cluster that the cluster at `cluster_index` has been merged into.
+fn find_root(roots: &mut [usize], mut cluster_index: usize) -> usize {
+    while roots[cluster_index] != cluster_index {
+        roots[cluster_index] = roots[roots[cluster_index]];
+        cluster_index = roots[cluster_index];
+    }
+    cluster_index
+}
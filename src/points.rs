@@ -0,0 +1,273 @@
+//! Built-in point types.
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use num_traits::Float;
+
+use crate::distance::{Cosine, DistanceFn, Euclidean};
+use crate::sync::MaybeSync;
+use crate::{Coordinates, MetricSpace, SubspaceMetric};
+
+/// A point in 2D Euclidean space with single precision.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Point2D {
+    /// The x coordinate.
+    pub x: f32,
+    /// The y coordinate.
+    pub y: f32,
+}
+
+impl Point2D {
+    /// Creates a new point at `(x, y)`.
+    pub fn new(x: f32, y: f32) -> Self {
+        Point2D { x, y }
+    }
+}
+
+impl From<(f32, f32)> for Point2D {
+    fn from((x, y): (f32, f32)) -> Self {
+        Point2D { x, y }
+    }
+}
+
+impl MetricSpace<f32> for Point2D {
+    const SQRT_OF_SQUARED: bool = true;
+
+    fn distance(&self, other: &Self) -> f32 {
+        self.distance_squared(other).sqrt()
+    }
+
+    fn distance_squared(&self, other: &Self) -> f32 {
+        (other.x - self.x).powi(2) + (other.y - self.y).powi(2)
+    }
+}
+
+impl Coordinates<2, f32> for Point2D {
+    fn coordinates(&self) -> [f32; 2] {
+        [self.x, self.y]
+    }
+}
+
+/// A point in 2D Euclidean space with double precision.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Point2D64 {
+    /// The x coordinate.
+    pub x: f64,
+    /// The y coordinate.
+    pub y: f64,
+}
+
+impl Point2D64 {
+    /// Creates a new point at `(x, y)`.
+    pub fn new(x: f64, y: f64) -> Self {
+        Point2D64 { x, y }
+    }
+}
+
+impl From<(f64, f64)> for Point2D64 {
+    fn from((x, y): (f64, f64)) -> Self {
+        Point2D64 { x, y }
+    }
+}
+
+impl MetricSpace for Point2D64 {
+    const SQRT_OF_SQUARED: bool = true;
+
+    fn distance(&self, other: &Self) -> f64 {
+        self.distance_squared(other).sqrt()
+    }
+
+    fn distance_squared(&self, other: &Self) -> f64 {
+        (other.x - self.x).powi(2) + (other.y - self.y).powi(2)
+    }
+}
+
+impl Coordinates<2> for Point2D64 {
+    fn coordinates(&self) -> [f64; 2] {
+        [self.x, self.y]
+    }
+}
+
+/// A point in N-dimensional space, whose distance is measured by `D`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NDPointWith<D, S = f64> {
+    /// The coordinates.
+    pub coords: Vec<S>,
+    #[serde(skip)]
+    distance: PhantomData<D>,
+}
+
+/// A point in N-dimensional Euclidean space.
+pub type NDPoint<S = f64> = NDPointWith<Euclidean, S>;
+
+impl<D: DistanceFn, S: Float> NDPointWith<D, S> {
+    /// Creates a new point at `coords`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `coords` is empty.
+    pub fn new(coords: Vec<S>) -> Self {
+        assert!(!coords.is_empty(), "coords must not be empty");
+        NDPointWith {
+            coords,
+            distance: PhantomData,
+        }
+    }
+}
+
+impl<D: DistanceFn + MaybeSync, S: Float + MaybeSync> MetricSpace<S> for NDPointWith<D, S> {
+    const SQRT_OF_SQUARED: bool = D::SQRT_OF_SQUARED;
+
+    fn distance(&self, other: &Self) -> S {
+        D::distance(&self.coords, &other.coords)
+    }
+
+    fn distance_squared(&self, other: &Self) -> S {
+        D::distance_squared(&self.coords, &other.coords)
+    }
+}
+
+/// An embedding vector, whose distance is the [cosine distance](Cosine).
+///
+/// Note that cosine distances are between `0` (same direction) and `2` (opposite direction),
+/// thus `eps_min` and `eps_max` are typically much smaller than for Euclidean distances.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CosinePoint<S = f64>(pub Vec<S>);
+
+impl<S: Float + MaybeSync> MetricSpace<S> for CosinePoint<S> {
+    fn distance(&self, other: &Self) -> S {
+        Cosine::distance(&self.0, &other.0)
+    }
+}
+
+/// A point in N-dimensional Euclidean space, whose distance only considers its active
+/// dimensions, e.g., the features that are relevant for its cluster.
+///
+/// The distance of two points is measured in the union of their active dimensions.
+///
+/// # Panics
+///
+/// Computing distances panics if both points have a different number of dimensions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubspacePoint<S = f64> {
+    /// The coordinates.
+    pub coords: Vec<S>,
+    /// The dimensions spanning the subspace.
+    pub active_dims: Vec<usize>,
+}
+
+impl<S: Float> SubspacePoint<S> {
+    /// Creates a new point at `coords`, whose distance only considers the `active_dims`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the `active_dims` is not a dimension of `coords`.
+    pub fn new(coords: Vec<S>, active_dims: Vec<usize>) -> Self {
+        assert!(
+            active_dims.iter().all(|&dim| dim < coords.len()),
+            "active_dims must be dimensions of coords"
+        );
+        SubspacePoint {
+            coords,
+            active_dims,
+        }
+    }
+}
+
+impl<S: Float + MaybeSync> MetricSpace<S> for SubspacePoint<S> {
+    fn distance(&self, other: &Self) -> S {
+        if self.active_dims == other.active_dims {
+            return self.subspace_distance(other, &self.active_dims);
+        }
+        let mut dims = [&self.active_dims[..], &other.active_dims[..]].concat();
+        dims.sort_unstable();
+        dims.dedup();
+        self.subspace_distance(other, &dims)
+    }
+}
+
+impl<S: Float + MaybeSync> SubspaceMetric<S> for SubspacePoint<S> {
+    fn subspace_distance(&self, other: &Self, dims: &[usize]) -> S {
+        assert_eq!(
+            self.coords.len(),
+            other.coords.len(),
+            "points must have the same number of dimensions"
+        );
+        dims.iter()
+            .fold(S::zero(), |sum, &dim| {
+                sum + (other.coords[dim] - self.coords[dim]).powi(2)
+            })
+            .sqrt()
+    }
+}
+
+/// A point whose distances are computed by a function.
+pub(crate) struct FnPoint<'a, P, F> {
+    pub(crate) point: &'a P,
+    pub(crate) distance_fn: &'a F,
+}
+
+impl<P: MaybeSync, S: Float, F: Fn(&P, &P) -> S + MaybeSync> MetricSpace<S> for FnPoint<'_, P, F> {
+    fn distance(&self, other: &Self) -> S {
+        (self.distance_fn)(self.point, other.point)
+    }
+}
+
+/// Fixed-size arrays are points in Euclidean space.
+impl<S: Float + MaybeSync, const N: usize> MetricSpace<S> for [S; N] {
+    const SQRT_OF_SQUARED: bool = true;
+
+    fn distance(&self, other: &Self) -> S {
+        self.distance_squared(other).sqrt()
+    }
+
+    fn distance_squared(&self, other: &Self) -> S {
+        self.iter()
+            .zip(other.iter())
+            .fold(S::zero(), |sum, (&a, &b)| sum + (b - a) * (b - a))
+    }
+}
+
+impl<S: Copy, const N: usize> Coordinates<N, S> for [S; N] {
+    fn coordinates(&self) -> [S; N] {
+        *self
+    }
+}
+
+/// Points of `nalgebra` are points in Euclidean space.
+#[cfg(feature = "nalgebra")]
+impl<S: Float + nalgebra::Scalar + MaybeSync, const D: usize> MetricSpace<S>
+    for nalgebra::Point<S, D>
+{
+    const SQRT_OF_SQUARED: bool = true;
+
+    fn distance(&self, other: &Self) -> S {
+        self.distance_squared(other).sqrt()
+    }
+
+    fn distance_squared(&self, other: &Self) -> S {
+        Euclidean::distance_squared(self.coords.as_slice(), other.coords.as_slice())
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<S: Float + nalgebra::Scalar, const D: usize> Coordinates<D, S> for nalgebra::Point<S, D> {
+    fn coordinates(&self) -> [S; D] {
+        self.coords.into()
+    }
+}
+
+/// One-dimensional views of `ndarray` are points in Euclidean space.
+#[cfg(feature = "ndarray")]
+impl<S: Float + MaybeSync> MetricSpace<S> for ndarray::ArrayView1<'_, S> {
+    const SQRT_OF_SQUARED: bool = true;
+
+    fn distance(&self, other: &Self) -> S {
+        self.distance_squared(other).sqrt()
+    }
+
+    fn distance_squared(&self, other: &Self) -> S {
+        self.iter()
+            .zip(other.iter())
+            .fold(S::zero(), |sum, (&a, &b)| sum + (b - a) * (b - a))
+    }
+}
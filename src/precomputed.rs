@@ -0,0 +1,115 @@
+//! Neighborhoods that are reused to cluster with different densities.
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::index::{BruteForce, SpatialIndex};
+use crate::{Cluster, Context, FuzzyDBSCAN, FuzzyDBSCANError, MetricSpace, Neighbor};
+
+/// The neighbors and densities of a list of points, as computed by
+/// [`FuzzyDBSCAN::precompute_neighbors`].
+pub struct PrecomputedNeighbors<'a, P, S = f64> {
+    points: &'a [P],
+    neighborhoods: Vec<(Vec<Neighbor<S>>, S)>,
+}
+
+impl<P, S> PrecomputedNeighbors<'_, P, S> {
+    /// Returns the points whose neighbors have been computed.
+    pub fn points(&self) -> &[P] {
+        self.points
+    }
+}
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Computes the neighbors and densities of a list of `points` once, to
+    /// [recluster](Self::recluster) them with different values of `pts_min` and `pts_max`.
+    ///
+    /// This takes `O(n²)` distance computations, just like clustering.
+    pub fn precompute_neighbors<'a, P: MetricSpace<S>>(
+        &self,
+        points: &'a [P],
+    ) -> Result<PrecomputedNeighbors<'a, P, S>, FuzzyDBSCANError> {
+        self.validate()?;
+        if points.is_empty() {
+            return Err(FuzzyDBSCANError::EmptyInput);
+        }
+        let context = Context {
+            points,
+            weights: None,
+            index: BruteForce::build(points, self.eps_max),
+            neighborhoods: Vec::new(),
+        };
+        Ok(PrecomputedNeighbors {
+            points,
+            neighborhoods: self.precompute_neighborhoods(&context),
+        })
+    }
+
+    /// Clusters the points of precomputed `neighbors` with the given `pts_min` and `pts_max`,
+    /// which does not compute any distances.
+    ///
+    /// All other parameters are taken from `self`, but the neighborhoods keep the radii of the
+    /// instance that computed them.
+    pub fn recluster<P: MetricSpace<S>>(
+        &self,
+        neighbors: &PrecomputedNeighbors<P, S>,
+        pts_min: S,
+        pts_max: S,
+    ) -> Result<Vec<Cluster<S>>, FuzzyDBSCANError> {
+        if !pts_min.is_finite() || !pts_max.is_finite() {
+            return Err(FuzzyDBSCANError::PtsNotFinite);
+        } else if pts_min < S::zero() || pts_min > pts_max {
+            return Err(FuzzyDBSCANError::InvalidPtsRange);
+        }
+        let points = neighbors.points;
+        let context = Context {
+            points,
+            weights: None,
+            index: BruteForce::build(points, self.eps_max),
+            neighborhoods: neighbors
+                .neighborhoods
+                .iter()
+                .map(|(neighbors, density)| {
+                    let label = self.core_membership.eval(*density, pts_min, pts_max);
+                    (neighbors.clone(), label)
+                })
+                .collect(),
+        };
+        Ok(self.expand_clusters(&context, None))
+    }
+
+    /// Clusters the points of precomputed `neighbors` with an `eps_max` of at most the one that
+    /// computed them, which only keeps the neighbors within `eps_max` and recomputes densities.
+    pub(crate) fn recluster_with_eps_max<P: MetricSpace<S>>(
+        &self,
+        neighbors: &PrecomputedNeighbors<P, S>,
+        eps_max: S,
+    ) -> Vec<Cluster<S>> {
+        let points = neighbors.points;
+        let mut context = Context {
+            points,
+            weights: None,
+            index: BruteForce::build(points, eps_max),
+            neighborhoods: Vec::new(),
+        };
+        let neighborhoods = neighbors
+            .neighborhoods
+            .iter()
+            .map(|(neighbors, _)| {
+                let neighbors: Vec<Neighbor<S>> = neighbors
+                    .iter()
+                    .filter(|&&(_, distance, _)| distance <= eps_max)
+                    .map(|&(neighbor_index, distance, _)| {
+                        let mu_distance =
+                            self.border_membership
+                                .eval(-distance, -eps_max, -self.eps_min);
+                        (neighbor_index, distance, mu_distance)
+                    })
+                    .collect();
+                let label = self.mu_min_p(self.density(&neighbors, &context));
+                (neighbors, label)
+            })
+            .collect();
+        context.neighborhoods = neighborhoods;
+        self.expand_clusters(&context, None)
+    }
+}
@@ -0,0 +1,94 @@
+//! Python bindings.
+use alloc::vec::Vec;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::{Category, FuzzyDBSCAN, Point2D64};
+
+/// An instance of the FuzzyDBSCAN algorithm for Python.
+#[pyclass(name = "FuzzyDBSCAN")]
+pub struct PyFuzzyDBSCAN {
+    /// The minimum fuzzy local neighborhood radius.
+    #[pyo3(get, set)]
+    pub eps_min: f64,
+    /// The maximum fuzzy local neighborhood radius.
+    #[pyo3(get, set)]
+    pub eps_max: f64,
+    /// The minimum fuzzy neighborhood density (number of points).
+    #[pyo3(get, set)]
+    pub pts_min: f64,
+    /// The maximum fuzzy neighborhood density (number of points).
+    #[pyo3(get, set)]
+    pub pts_max: f64,
+    /// The minimum number of points per cluster, smaller clusters are reclassified as noise.
+    #[pyo3(get, set)]
+    pub min_cluster_size: usize,
+}
+
+#[pymethods]
+impl PyFuzzyDBSCAN {
+    /// Creates a new instance of the algorithm with the given parameters.
+    #[new]
+    fn new(eps_min: f64, eps_max: f64, pts_min: f64, pts_max: f64) -> Self {
+        PyFuzzyDBSCAN {
+            eps_min,
+            eps_max,
+            pts_min,
+            pts_max,
+            min_cluster_size: FuzzyDBSCAN::<f64>::default().min_cluster_size,
+        }
+    }
+
+    /// Clusters 2D `points` and returns a list of assignments per cluster, whose keys match the
+    /// JSON serialization of [`Assignment`](crate::Assignment), raising a `ValueError` if that
+    /// fails.
+    fn cluster<'py>(
+        &self,
+        py: Python<'py>,
+        points: Vec<(f64, f64)>,
+    ) -> PyResult<Vec<Vec<Bound<'py, PyDict>>>> {
+        let points: Vec<Point2D64> = points
+            .into_iter()
+            .map(|(x, y)| Point2D64::new(x, y))
+            .collect();
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min: self.eps_min,
+            eps_max: self.eps_max,
+            pts_min: self.pts_min,
+            pts_max: self.pts_max,
+            min_cluster_size: self.min_cluster_size,
+            ..Default::default()
+        };
+        let clusters = fuzzy_dbscan
+            .cluster(&points)
+            .map_err(|error| PyValueError::new_err(error.to_string()))?;
+        clusters
+            .iter()
+            .map(|cluster| {
+                cluster
+                    .iter()
+                    .map(|a| {
+                        let assignment = PyDict::new(py);
+                        assignment.set_item("index", a.index)?;
+                        assignment.set_item("cluster_id", a.cluster_id)?;
+                        assignment.set_item("label", a.label)?;
+                        let category = match a.category {
+                            Category::Core => "core",
+                            Category::Border => "border",
+                            Category::Noise => "noise",
+                        };
+                        assignment.set_item("category", category)?;
+                        Ok(assignment)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// The Python module, which is built by maturin with the `python` feature.
+#[pymodule]
+fn fuzzy_dbscan(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyFuzzyDBSCAN>()
+}
@@ -0,0 +1,118 @@
+//! Queries of points by their category.
+use crate::{Assignment, Category, Cluster};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use num_traits::Float;
+
+/// Returns the sorted indices of all points with the given `category`, without duplicates.
+fn points_by_category<S>(clusters: &[Cluster<S>], category: Category) -> Vec<usize> {
+    let mut indices: Vec<usize> = clusters
+        .iter()
+        .flatten()
+        .filter(|a| a.category == category)
+        .map(|a| a.index)
+        .collect();
+    indices.sort_unstable();
+    indices.dedup();
+    indices
+}
+
+/// Returns the sorted indices of all core points.
+pub fn core_points<S>(clusters: &[Cluster<S>]) -> Vec<usize> {
+    points_by_category(clusters, Category::Core)
+}
+
+/// Returns the sorted indices of all border points, even if they belong to multiple clusters.
+pub fn border_points<S>(clusters: &[Cluster<S>]) -> Vec<usize> {
+    points_by_category(clusters, Category::Border)
+}
+
+/// Returns the sorted indices of all noise points.
+pub fn noise_points<S>(clusters: &[Cluster<S>]) -> Vec<usize> {
+    points_by_category(clusters, Category::Noise)
+}
+
+/// Returns the sorted indices of the points in each cluster, without duplicates.
+///
+/// The noise cluster, if any, comes last, so that the remaining indices match the cluster
+/// indices.
+pub fn cluster_indices<S>(clusters: &[Cluster<S>]) -> Vec<Vec<usize>> {
+    let is_noise = |cluster: &Cluster<S>| cluster.iter().all(|a| a.category == Category::Noise);
+    let (noise, clusters): (Vec<_>, Vec<_>) = clusters
+        .iter()
+        .filter(|cluster| !cluster.is_empty())
+        .partition(|cluster| is_noise(cluster));
+    clusters
+        .into_iter()
+        .chain(noise)
+        .map(|cluster| {
+            let mut indices: Vec<usize> = cluster.iter().map(|a| a.index).collect();
+            indices.sort_unstable();
+            indices.dedup();
+            indices
+        })
+        .collect()
+}
+
+/// Returns the `n` assignments of a `cluster` with the highest labels, in descending order.
+///
+/// Assignments with a NaN label are ranked last.
+pub fn top_n<S: Float>(cluster: &Cluster<S>, n: usize) -> Vec<&Assignment<S>> {
+    let mut assignments: Vec<&Assignment<S>> = cluster.iter().collect();
+    assignments.sort_by(|a, b| {
+        a.partial_cmp(b)
+            .unwrap_or_else(|| a.label.is_nan().cmp(&b.label.is_nan()))
+    });
+    assignments.truncate(n);
+    assignments
+}
+
+/// Returns the number of assignments, core points, border points, and noise points of each
+/// cluster, including the noise cluster.
+pub fn cluster_sizes<S>(clusters: &[Cluster<S>]) -> Vec<(usize, usize, usize, usize)> {
+    clusters
+        .iter()
+        .map(|cluster| {
+            let count =
+                |category: Category| cluster.iter().filter(|a| a.category == category).count();
+            (
+                cluster.len(),
+                count(Category::Core),
+                count(Category::Border),
+                count(Category::Noise),
+            )
+        })
+        .collect()
+}
+
+/// Returns the index of the non-noise cluster with the most assignments, preferring the first
+/// one on ties.
+pub fn largest_cluster<S>(clusters: &[Cluster<S>]) -> Option<usize> {
+    clusters
+        .iter()
+        .enumerate()
+        .filter(|(_, cluster)| cluster.iter().any(|a| a.category != Category::Noise))
+        .rev()
+        .max_by_key(|(_, cluster)| cluster.len())
+        .map(|(cluster_index, _)| cluster_index)
+}
+
+/// Returns the `(cluster_index, label, category)` of every cluster that each point belongs to,
+/// in the order of the clusters.
+///
+/// Points are only listed if they belong to a cluster, i.e., noise points are left out.
+pub fn point_to_clusters<S: Copy>(
+    clusters: &[Cluster<S>],
+) -> BTreeMap<usize, Vec<(usize, S, Category)>> {
+    let mut memberships: BTreeMap<usize, Vec<(usize, S, Category)>> = BTreeMap::new();
+    for (cluster_index, cluster) in clusters.iter().enumerate() {
+        for assignment in cluster.iter().filter(|a| a.category != Category::Noise) {
+            memberships.entry(assignment.index).or_default().push((
+                cluster_index,
+                assignment.label,
+                assignment.category.clone(),
+            ));
+        }
+    }
+    memberships
+}
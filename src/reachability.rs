@@ -0,0 +1,119 @@
+//! Reachability orderings, as computed by the OPTICS algorithm.
+use alloc::vec;
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::index::{BruteForce, SpatialIndex};
+use crate::{FuzzyDBSCAN, FuzzyDBSCANError, MetricSpace};
+
+/// An element of a reachability ordering.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReachabilityEntry<S = f64> {
+    /// The point index.
+    pub point_index: usize,
+    /// The smallest radius that makes the point a core point, or infinity if there is none.
+    pub core_distance: S,
+    /// The smallest reachability distance from any preceding point, or infinity if there is none.
+    pub reachability_distance: S,
+}
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Orders a list of `points` by their reachability, as defined by the OPTICS algorithm.
+    ///
+    /// Neighborhoods are limited to `eps_max` and a core point needs `pts_min` (rounded up)
+    /// points within its core distance, including itself. Valleys in the reachability distances
+    /// of the ordering correspond to clusters for any radius up to `eps_max`.
+    pub fn reachability_order<P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+    ) -> Result<Vec<ReachabilityEntry<S>>, FuzzyDBSCANError> {
+        self.validate()?;
+        if points.is_empty() {
+            return Err(FuzzyDBSCANError::EmptyInput);
+        }
+        let index: BruteForce<S> = SpatialIndex::<P, S>::build(points, self.eps_max);
+        let min_neighbors = self
+            .pts_min
+            .ceil()
+            .to_usize()
+            .unwrap_or(0)
+            .saturating_sub(1);
+        let mut processed = vec![false; points.len()];
+        let mut reachability = vec![S::infinity(); points.len()];
+        let mut order = Vec::with_capacity(points.len());
+        for start_index in 0..points.len() {
+            if processed[start_index] {
+                continue;
+            }
+            let mut seeds = vec![start_index];
+            while let Some(seed_position) = (0..seeds.len()).min_by(|&a, &b| {
+                reachability[seeds[a]]
+                    .partial_cmp(&reachability[seeds[b]])
+                    .unwrap()
+            }) {
+                let point_index = seeds.swap_remove(seed_position);
+                processed[point_index] = true;
+                let neighbors = index.region_query_with_distances(points, point_index);
+                let core_distance = self.core_distance(&neighbors, min_neighbors);
+                order.push(ReachabilityEntry {
+                    point_index,
+                    core_distance,
+                    reachability_distance: reachability[point_index],
+                });
+                if core_distance.is_infinite() {
+                    continue;
+                }
+                for (neighbor_index, distance) in neighbors {
+                    if processed[neighbor_index] {
+                        continue;
+                    }
+                    let reachability_distance = core_distance.max(distance);
+                    if reachability[neighbor_index].is_infinite() {
+                        seeds.push(neighbor_index);
+                    }
+                    if reachability_distance < reachability[neighbor_index] {
+                        reachability[neighbor_index] = reachability_distance;
+                    }
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Computes the mutual reachability distance `max(core(p), core(q), d(p, q))` of the points
+    /// at the indices `p` and `q`, whose core distances are the distances to their `k`-th nearest
+    /// neighbor within `eps_max`, or infinity if there are less neighbors.
+    ///
+    /// Unlike [`reachability_order`](Self::reachability_order), the core distance does not
+    /// depend on `pts_min`.
+    pub fn reachability_distance<P: MetricSpace<S>>(
+        &self,
+        points: &[P],
+        p: usize,
+        q: usize,
+        k: usize,
+    ) -> S {
+        let index: BruteForce<S> = SpatialIndex::<P, S>::build(points, self.eps_max);
+        let core_distance = |point_index: usize| {
+            let neighbors = index.region_query_with_distances(points, point_index);
+            self.core_distance(&neighbors, k)
+        };
+        points[p]
+            .distance(&points[q])
+            .max(core_distance(p))
+            .max(core_distance(q))
+    }
+
+    fn core_distance(&self, neighbors: &[(usize, S)], min_neighbors: usize) -> S {
+        if min_neighbors == 0 {
+            return S::zero();
+        }
+        if neighbors.len() < min_neighbors {
+            return S::infinity();
+        }
+        let mut distances: Vec<S> = neighbors.iter().map(|&(_, distance)| distance).collect();
+        let (_, core_distance, _) =
+            distances.select_nth_unstable_by(min_neighbors - 1, |a, b| a.partial_cmp(b).unwrap());
+        *core_distance
+    }
+}
@@ -0,0 +1,97 @@
+//! Results of clustering runs.
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use crate::{Category, Cluster};
+
+/// The clusters found by [clustering](crate::FuzzyDBSCAN::cluster), where the noise cluster (if
+/// any) comes last.
+///
+/// Results dereference to a slice of all clusters, including the noise cluster.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ClusterResult<S = f64>(Vec<Cluster<S>>);
+
+impl<S: Copy> ClusterResult<S> {
+    /// Returns all clusters except for the noise cluster.
+    pub fn real_clusters(&self) -> &[Cluster<S>] {
+        match self.noise() {
+            Some(_) => &self.0[..self.0.len() - 1],
+            None => &self.0,
+        }
+    }
+
+    /// Returns the noise cluster, if any point is noise.
+    pub fn noise(&self) -> Option<&Cluster<S>> {
+        self.0
+            .last()
+            .filter(|cluster| cluster.iter().all(|a| a.category == Category::Noise))
+    }
+
+    /// Returns the number of clusters, excluding the noise cluster.
+    pub fn cluster_count(&self) -> usize {
+        self.real_clusters().len()
+    }
+
+    /// Returns the index of the first cluster containing the point at `point_index` and its
+    /// label in that cluster, or `None` if the point is noise.
+    pub fn point_cluster(&self, point_index: usize) -> Option<(usize, S)> {
+        self.real_clusters()
+            .iter()
+            .enumerate()
+            .find_map(|(cluster_index, cluster)| {
+                cluster
+                    .iter()
+                    .find(|a| a.index == point_index)
+                    .map(|a| (cluster_index, a.label))
+            })
+    }
+
+    /// Returns whether the point at `point_index` is not part of any cluster.
+    pub fn is_noise(&self, point_index: usize) -> bool {
+        self.point_cluster(point_index).is_none()
+    }
+
+    /// Returns all clusters, including the noise cluster.
+    pub fn into_clusters(self) -> Vec<Cluster<S>> {
+        self.0
+    }
+}
+
+impl<S> Deref for ClusterResult<S> {
+    type Target = [Cluster<S>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S> From<Vec<Cluster<S>>> for ClusterResult<S> {
+    fn from(clusters: Vec<Cluster<S>>) -> Self {
+        ClusterResult(clusters)
+    }
+}
+
+impl<S> From<ClusterResult<S>> for Vec<Cluster<S>> {
+    fn from(result: ClusterResult<S>) -> Self {
+        result.0
+    }
+}
+
+impl<S> IntoIterator for ClusterResult<S> {
+    type Item = Cluster<S>;
+    type IntoIter = alloc::vec::IntoIter<Cluster<S>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, S> IntoIterator for &'a ClusterResult<S> {
+    type Item = &'a Cluster<S>;
+    type IntoIter = core::slice::Iter<'a, Cluster<S>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
@@ -0,0 +1,82 @@
+//! Spectral embeddings of distance matrices.
+use alloc::vec::Vec;
+use nalgebra::{DMatrix, SymmetricEigen};
+use num_traits::Float;
+
+use crate::DistanceMatrix;
+
+/// The rank of the neighbor whose distance scales the affinities of a point.
+const SCALE_NEIGHBOR: usize = 7;
+
+/// Maps the points of a `distance_matrix` to `target_dim` dimensions, e.g., to cluster
+/// high-dimensional data as [`NDPoint`](crate::NDPoint)s.
+///
+/// The coordinates are the eigenvectors of the normalized Laplacian of the neighborhood graph
+/// with the smallest eigenvalues, except for the trivial one. Neighbors are weighted by Gaussian
+/// affinities, which are scaled by the distances of both points to their 7th nearest neighbor.
+///
+/// # Panics
+///
+/// Panics if `target_dim` is not less than the number of points.
+pub fn spectral_embedding<S: Float>(
+    distance_matrix: &DistanceMatrix<S>,
+    target_dim: usize,
+) -> Vec<Vec<S>> {
+    let size = distance_matrix.len();
+    assert!(
+        target_dim < size,
+        "target dimension is not less than the number of points"
+    );
+    let distance = |i: usize, j: usize| distance_matrix[i][j].to_f64().unwrap();
+    let scales: Vec<f64> = (0..size)
+        .map(|i| {
+            let mut distances: Vec<f64> = (0..size)
+                .filter(|&j| j != i)
+                .map(|j| distance(i, j))
+                .collect();
+            distances.sort_by(f64::total_cmp);
+            let scale = distances.get(SCALE_NEIGHBOR - 1).or(distances.last());
+            scale.copied().unwrap_or(0.0)
+        })
+        .collect();
+    let affinities = DMatrix::from_fn(size, size, |i, j| {
+        let (distance, scale) = (distance(i, j), scales[i] * scales[j]);
+        if i == j {
+            0.0
+        } else if scale > 0.0 {
+            (-distance * distance / scale).exp()
+        } else if distance == 0.0 {
+            1.0
+        } else {
+            0.0
+        }
+    });
+    let degrees: Vec<f64> = affinities.row_iter().map(|row| row.sum()).collect();
+    // The largest eigenvalues of D^-½ W D^-½ are the smallest of the Laplacian I - D^-½ W D^-½.
+    let normalized = DMatrix::from_fn(size, size, |i, j| {
+        let degree = (degrees[i] * degrees[j]).sqrt();
+        if degree > 0.0 {
+            affinities[(i, j)] / degree
+        } else {
+            0.0
+        }
+    });
+    let eigen = SymmetricEigen::new(normalized);
+    // Eigenvalues are unordered, thus sort them descending and skip the first (trivial) one.
+    let mut order: Vec<usize> = (0..size).collect();
+    order.sort_by(|&a, &b| eigen.eigenvalues[b].total_cmp(&eigen.eigenvalues[a]));
+    (0..size)
+        .map(|i| {
+            order[1..=target_dim]
+                .iter()
+                .map(|&k| {
+                    let coordinate = match degrees[i] {
+                        degree if degree > 0.0 => eigen.eigenvectors[(i, k)] / degree.sqrt(),
+                        _ => 0.0,
+                    };
+                    S::from(coordinate).unwrap()
+                })
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,249 @@
+//! Incremental clustering with cached neighborhoods.
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::index::{BruteForce, SpatialIndex};
+use crate::{
+    Assignment, BorderLabelMode, Category, Cluster, ClusterUpdate, Context, FuzzyDBSCAN,
+    FuzzyDBSCANError, MetricSpace, Neighbor,
+};
+
+/// The state of clustering a growing set of points, which caches the neighborhoods and
+/// assignments of all points added so far.
+///
+/// Clusters keep their identifier until they are merged. All clusters are kept regardless of
+/// `min_cluster_size`, as they may still grow. Note that a state must always be
+/// [updated](FuzzyDBSCAN::update) with the same parameters.
+pub struct ClusterState<P, S = f64> {
+    points: Vec<P>,
+    /// The neighbors and core labels of all points.
+    neighborhoods: Vec<(Vec<Neighbor<S>>, S)>,
+    /// The cluster identifiers of all core points.
+    core_cluster_ids: Vec<Option<usize>>,
+    clusters: Vec<(usize, Cluster<S>)>,
+    noise: Cluster<S>,
+    next_cluster_id: usize,
+}
+
+impl<P, S> Default for ClusterState<P, S> {
+    fn default() -> Self {
+        ClusterState {
+            points: Vec::new(),
+            neighborhoods: Vec::new(),
+            core_cluster_ids: Vec::new(),
+            clusters: Vec::new(),
+            noise: Vec::new(),
+            next_cluster_id: 0,
+        }
+    }
+}
+
+impl<P, S> ClusterState<P, S> {
+    /// Creates a state without any points.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns all points added so far.
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    /// Returns the clusters and their identifiers.
+    pub fn clusters(&self) -> &[(usize, Cluster<S>)] {
+        &self.clusters
+    }
+
+    /// Returns the points classified as noise, ordered by index.
+    pub fn noise(&self) -> &Cluster<S> {
+        &self.noise
+    }
+}
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Adds `new_points` to the `state` and returns how the clustering changed.
+    ///
+    /// Only the neighborhoods of the new points are queried. Adding points only increases
+    /// densities, thus cores remain cores and only clusters with cores whose neighborhoods have
+    /// changed are expanded again, which then can only grow or merge.
+    pub fn update<P: MetricSpace<S> + Clone>(
+        &self,
+        state: &mut ClusterState<P, S>,
+        new_points: &[P],
+    ) -> Result<Vec<ClusterUpdate<S>>, FuzzyDBSCANError> {
+        self.validate()?;
+        Ok(self.extend_state(state, new_points.iter().cloned()))
+    }
+
+    /// Adds `new_points` to the `state` without validating the parameters, see
+    /// [`update`](Self::update).
+    pub(crate) fn extend_state<P: MetricSpace<S>>(
+        &self,
+        state: &mut ClusterState<P, S>,
+        new_points: impl IntoIterator<Item = P>,
+    ) -> Vec<ClusterUpdate<S>> {
+        let old_len = state.points.len();
+        state.points.extend(new_points);
+        let context = Context {
+            points: &state.points[..],
+            weights: None,
+            index: BruteForce::build(&state.points, self.eps_max),
+            neighborhoods: Vec::new(),
+        };
+        // The new points and their neighbors, whose densities have changed.
+        let mut affected = BTreeSet::new();
+        for point_index in old_len..state.points.len() {
+            let neighbors = self.fuzzy_region_query(point_index, &context);
+            for &(neighbor_index, distance, mu_distance) in &neighbors {
+                if neighbor_index < old_len {
+                    let neighborhood = &mut state.neighborhoods[neighbor_index];
+                    neighborhood.0.push((point_index, distance, mu_distance));
+                    affected.insert(neighbor_index);
+                }
+            }
+            state.neighborhoods.push((neighbors, S::zero()));
+            state.core_cluster_ids.push(None);
+            affected.insert(point_index);
+        }
+        for &point_index in &affected {
+            let density = self.density(&state.neighborhoods[point_index].0, &context);
+            state.neighborhoods[point_index].1 = self.mu_min_p(density);
+        }
+        // Collect the cores connected to affected cores and their non-core neighbors.
+        let neighborhoods = &state.neighborhoods;
+        let is_core = |point_index: usize| self.is_core_label(neighborhoods[point_index].1);
+        let mut expanded = vec![false; state.points.len()];
+        let mut components = Vec::new();
+        for &seed_index in &affected {
+            if !is_core(seed_index) || expanded[seed_index] {
+                continue;
+            }
+            expanded[seed_index] = true;
+            let mut cores = vec![seed_index];
+            let mut queue = vec![seed_index];
+            let mut border_indices = BTreeSet::new();
+            while let Some(point_index) = queue.pop() {
+                for &(neighbor_index, _, _) in &neighborhoods[point_index].0 {
+                    if !is_core(neighbor_index) {
+                        border_indices.insert(neighbor_index);
+                    } else if !expanded[neighbor_index] {
+                        expanded[neighbor_index] = true;
+                        cores.push(neighbor_index);
+                        queue.push(neighbor_index);
+                    }
+                }
+            }
+            components.push((cores, border_indices));
+        }
+        let mut updates = Vec::new();
+        let mut clustered = vec![false; state.points.len()];
+        let mut dropped = Vec::new();
+        for (cores, border_indices) in components {
+            let mut ids: Vec<usize> = cores
+                .iter()
+                .filter_map(|&point_index| state.core_cluster_ids[point_index])
+                .collect();
+            ids.sort_unstable();
+            ids.dedup();
+            let cluster_id = match ids.first() {
+                Some(&cluster_id) => cluster_id,
+                None => {
+                    let cluster_id = state.next_cluster_id;
+                    state.next_cluster_id += 1;
+                    updates.push(ClusterUpdate::NewClusterCreated { cluster_id });
+                    cluster_id
+                }
+            };
+            let mut cluster = Vec::new();
+            for &point_index in &cores {
+                state.core_cluster_ids[point_index] = Some(cluster_id);
+                cluster.push(Assignment {
+                    index: point_index,
+                    cluster_id: Some(cluster_id),
+                    category: Category::Core,
+                    label: state.neighborhoods[point_index].1,
+                });
+            }
+            // Only cores within eps_max affect the label, which are all neighbors of the border point.
+            for border_index in border_indices {
+                let mut label = None;
+                for &(neighbor_index, _, mu_distance) in &state.neighborhoods[border_index].0 {
+                    let core_label = state.neighborhoods[neighbor_index].1;
+                    let is_member = state.core_cluster_ids[neighbor_index] == Some(cluster_id);
+                    if is_member && core_label > S::zero() && mu_distance > S::zero() {
+                        let contribution = core_label.min(mu_distance);
+                        label = Some(match (label, self.border_label_mode) {
+                            (None, _) => contribution,
+                            (Some(label), BorderLabelMode::MinCore) => contribution.min(label),
+                            (Some(label), BorderLabelMode::MaxCore) => contribution.max(label),
+                        });
+                    }
+                }
+                let label = label.unwrap_or_else(S::zero);
+                if label < self.border_threshold {
+                    dropped.push(border_index);
+                    continue;
+                }
+                clustered[border_index] = true;
+                cluster.push(Assignment {
+                    index: border_index,
+                    cluster_id: Some(cluster_id),
+                    category: Category::Border,
+                    label,
+                });
+            }
+            let previous_len = state
+                .clusters
+                .iter()
+                .filter(|(id, _)| ids.contains(id))
+                .map(|(_, cluster)| cluster.len())
+                .sum::<usize>();
+            if ids.len() > 1 {
+                updates.push(ClusterUpdate::ClustersWereMerged { ids: ids.clone() });
+            } else if !ids.is_empty() && cluster.len() > previous_len {
+                updates.push(ClusterUpdate::ClusterExpanded { cluster_id });
+            }
+            // Replace the first merged cluster and remove the others.
+            state
+                .clusters
+                .retain(|(id, _)| *id == cluster_id || !ids.contains(id));
+            match state.clusters.iter_mut().find(|(id, _)| *id == cluster_id) {
+                Some((_, old_cluster)) => *old_cluster = cluster,
+                None => state.clusters.push((cluster_id, cluster)),
+            }
+        }
+        // Only previous noise, new points, and border points below the threshold in expanded
+        // clusters may be noise, as other border points remain borders.
+        let mut noise_indices: Vec<usize> = state.noise.iter().map(|a| a.index).collect();
+        noise_indices.extend(old_len..state.points.len());
+        noise_indices.extend(dropped.into_iter().filter(|&point_index| {
+            let mut assignments = state.clusters.iter().flat_map(|(_, cluster)| cluster);
+            !assignments.any(|a| a.index == point_index)
+        }));
+        noise_indices.sort_unstable();
+        noise_indices.dedup();
+        state.noise = noise_indices
+            .into_iter()
+            .filter(|&point_index| {
+                state.core_cluster_ids[point_index].is_none() && !clustered[point_index]
+            })
+            .map(|point_index| Assignment {
+                index: point_index,
+                cluster_id: None,
+                category: Category::Noise,
+                label: S::one(),
+            })
+            .collect();
+        for (cluster_id, cluster) in &state.clusters {
+            for assignment in cluster.iter().filter(|a| a.index >= old_len) {
+                updates.push(ClusterUpdate::PointAddedToCluster {
+                    cluster_id: *cluster_id,
+                    assignment: assignment.clone(),
+                });
+            }
+        }
+        updates
+    }
+}
@@ -0,0 +1,67 @@
+//! Clustering in subspaces of high-dimensional points.
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::sync::MaybeSync;
+use crate::{metrics, ClusterResult, FuzzyDBSCAN, FuzzyDBSCANError, MetricSpace};
+
+/// A trait to compute distances of scalar type `S` between points, which only consider some of
+/// their dimensions.
+pub trait SubspaceMetric<S: Float = f64>: Sized + MaybeSync {
+    /// Returns the distance to `other` in the subspace spanned by the dimensions `dims`.
+    fn subspace_distance(&self, other: &Self, dims: &[usize]) -> S;
+}
+
+/// The clusters found in a subspace by [`FuzzyDBSCAN::subspace_cluster`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubspaceClusters<S = f64> {
+    /// The dimensions spanning the subspace.
+    pub dims: Vec<usize>,
+    /// The clusters, including noise.
+    pub clusters: ClusterResult<S>,
+    /// The [silhouette score](metrics::silhouette_score) of the clusters within the subspace,
+    /// higher is better.
+    pub silhouette: S,
+}
+
+/// A point projected into the subspace spanned by `dims`.
+struct ProjectedPoint<'a, P> {
+    point: &'a P,
+    dims: &'a [usize],
+}
+
+impl<S: Float, P: SubspaceMetric<S>> MetricSpace<S> for ProjectedPoint<'_, P> {
+    fn distance(&self, other: &Self) -> S {
+        self.point.subspace_distance(other.point, self.dims)
+    }
+}
+
+impl<S: Float + Send + Sync> FuzzyDBSCAN<S> {
+    /// Clusters a list of `points` in each of the `subspaces`, which are given by the dimensions
+    /// spanning them, e.g., to find clusters that are only separated by some features.
+    ///
+    /// Returns the clusters of each subspace in the given order, together with their silhouette
+    /// score to compare subspaces.
+    pub fn subspace_cluster<P: SubspaceMetric<S>>(
+        &self,
+        points: &[P],
+        subspaces: &[Vec<usize>],
+    ) -> Result<Vec<SubspaceClusters<S>>, FuzzyDBSCANError> {
+        subspaces
+            .iter()
+            .map(|dims| {
+                let projected: Vec<_> = points
+                    .iter()
+                    .map(|point| ProjectedPoint { point, dims })
+                    .collect();
+                let clusters = self.cluster(&projected)?;
+                let silhouette = metrics::silhouette_score(&projected, &clusters);
+                Ok(SubspaceClusters {
+                    dims: dims.clone(),
+                    clusters,
+                    silhouette,
+                })
+            })
+            .collect()
+    }
+}
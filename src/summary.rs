@@ -0,0 +1,143 @@
+//! Summaries of clustering results.
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::fmt;
+use num_traits::Float;
+
+use crate::{Category, Cluster, Coordinates};
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Category::Core => "Core",
+            Category::Border => "Border",
+            Category::Noise => "Noise",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A summary of a [cluster](Cluster).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterSummary<S = f64> {
+    /// The cluster index.
+    pub cluster_id: usize,
+    /// The number of core points.
+    pub core_count: usize,
+    /// The number of border points.
+    pub border_count: usize,
+    /// The number of noise points.
+    pub noise_count: usize,
+    /// The mean label of all points.
+    pub mean_label: S,
+}
+
+/// Prints the summary as a table row of cluster index, counts, and mean label.
+impl<S: Float + fmt::Display> fmt::Display for ClusterSummary<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cluster {:>4} | core {:>6} | border {:>6} | noise {:>6} | mean label {:.3}",
+            self.cluster_id, self.core_count, self.border_count, self.noise_count, self.mean_label
+        )
+    }
+}
+
+/// Summarizes each of the `clusters`.
+pub fn summarize<S: Float>(clusters: &[Cluster<S>]) -> Vec<ClusterSummary<S>> {
+    clusters
+        .iter()
+        .enumerate()
+        .map(|(cluster_id, cluster)| {
+            let count = |category| cluster.iter().filter(|a| a.category == category).count();
+            let label_sum = cluster.iter().fold(S::zero(), |sum, a| sum + a.label);
+            ClusterSummary {
+                cluster_id,
+                core_count: count(Category::Core),
+                border_count: count(Category::Border),
+                noise_count: count(Category::Noise),
+                mean_label: label_sum / S::from(cluster.len()).unwrap(),
+            }
+        })
+        .collect()
+}
+
+/// Computes the label-weighted mean position of the non-noise points of each of the `clusters`.
+///
+/// Returns `None` for clusters without non-noise points, e.g., the noise cluster.
+pub fn cluster_centroids<S: Float, P: Coordinates<K, S>, const K: usize>(
+    points: &[P],
+    clusters: &[Cluster<S>],
+) -> Vec<Option<[S; K]>> {
+    clusters
+        .iter()
+        .map(|cluster| {
+            let mut seen = BTreeSet::new();
+            let mut weight_sum = S::zero();
+            let mut centroid = [S::zero(); K];
+            for a in cluster {
+                if a.category == Category::Noise || !seen.insert(a.index) {
+                    continue;
+                }
+                let coordinates = points[a.index].coordinates();
+                for (sum, coordinate) in centroid.iter_mut().zip(coordinates.iter()) {
+                    *sum = *sum + a.label * *coordinate;
+                }
+                weight_sum = weight_sum + a.label;
+            }
+            if weight_sum > S::zero() {
+                Some(centroid.map(|sum| sum / weight_sum))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Statistics of the labels of some assignments.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelStats<S = f64> {
+    /// The mean label.
+    pub mean: S,
+    /// The population standard deviation of the labels.
+    pub std: S,
+    /// The minimum label.
+    pub min: S,
+    /// The maximum label.
+    pub max: S,
+    /// The number of assignments.
+    pub count: usize,
+}
+
+/// Computes the statistics of the labels of all assignments of a `cluster` with the given
+/// `category`.
+///
+/// All statistics except for the count are NaN if there are no such assignments.
+pub fn label_statistics<S: Float>(cluster: &Cluster<S>, category: Category) -> LabelStats<S> {
+    let labels: Vec<S> = cluster
+        .iter()
+        .filter(|a| a.category == category)
+        .map(|a| a.label)
+        .collect();
+    if labels.is_empty() {
+        return LabelStats {
+            mean: S::nan(),
+            std: S::nan(),
+            min: S::nan(),
+            max: S::nan(),
+            count: 0,
+        };
+    }
+    let count = S::from(labels.len()).unwrap();
+    let mean = labels.iter().fold(S::zero(), |sum, &label| sum + label) / count;
+    let variance = labels.iter().fold(S::zero(), |sum, &label| {
+        sum + (label - mean) * (label - mean)
+    }) / count;
+    LabelStats {
+        mean,
+        std: variance.sqrt(),
+        min: labels.iter().copied().fold(S::infinity(), S::min),
+        max: labels.iter().copied().fold(S::neg_infinity(), S::max),
+        count: labels.len(),
+    }
+}
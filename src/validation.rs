@@ -0,0 +1,150 @@
+//! Validation of clustering results.
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+use num_traits::Float;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::{Category, Cluster};
+
+/// A violated invariant of a clustering result, given by the position of the cluster and the
+/// index of the point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationError {
+    /// A point index is not less than the number of points.
+    IndexOutOfRange {
+        /// The position of the cluster.
+        cluster_index: usize,
+        /// The index of the point.
+        point_index: usize,
+    },
+    /// A label is NaN or outside of `[0, 1]`.
+    LabelOutOfRange {
+        /// The position of the cluster.
+        cluster_index: usize,
+        /// The index of the point.
+        point_index: usize,
+    },
+    /// A noise point has a label other than `1`.
+    NoiseLabelNotOne {
+        /// The position of the cluster.
+        cluster_index: usize,
+        /// The index of the point.
+        point_index: usize,
+    },
+    /// A point is assigned more than once to the same cluster with different labels.
+    ConflictingLabels {
+        /// The position of the cluster.
+        cluster_index: usize,
+        /// The index of the point.
+        point_index: usize,
+    },
+    /// A cluster has no assignments.
+    EmptyCluster {
+        /// The position of the cluster.
+        cluster_index: usize,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::IndexOutOfRange {
+                cluster_index,
+                point_index,
+            } => write!(
+                f,
+                "point {} of cluster {} is out of range",
+                point_index, cluster_index
+            ),
+            ValidationError::LabelOutOfRange {
+                cluster_index,
+                point_index,
+            } => write!(
+                f,
+                "the label of point {} in cluster {} is outside of [0, 1]",
+                point_index, cluster_index
+            ),
+            ValidationError::NoiseLabelNotOne {
+                cluster_index,
+                point_index,
+            } => write!(
+                f,
+                "the label of noise point {} in cluster {} is not 1",
+                point_index, cluster_index
+            ),
+            ValidationError::ConflictingLabels {
+                cluster_index,
+                point_index,
+            } => write!(
+                f,
+                "point {} has different labels in cluster {}",
+                point_index, cluster_index
+            ),
+            ValidationError::EmptyCluster { cluster_index } => {
+                write!(f, "cluster {} is empty", cluster_index)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ValidationError {}
+
+/// Checks the invariants of `clusters` of `n_points` points, e.g., in debug assertions or tests,
+/// and returns all violations in the order of the clusters and their assignments.
+///
+/// Every point index must be less than `n_points`, every label must be in `[0, 1]` and `1` for
+/// noise points, a point must not be assigned to the same cluster with different labels, and no
+/// cluster must be empty.
+pub fn validate_clusters<S: Float>(
+    n_points: usize,
+    clusters: &[Cluster<S>],
+) -> Result<(), Vec<ValidationError>> {
+    let mut errors = Vec::new();
+    for (cluster_index, cluster) in clusters.iter().enumerate() {
+        if cluster.is_empty() {
+            errors.push(ValidationError::EmptyCluster { cluster_index });
+        }
+        let mut labels: BTreeMap<usize, S> = BTreeMap::new();
+        for assignment in cluster {
+            let point_index = assignment.index;
+            let label = assignment.label;
+            if point_index >= n_points {
+                errors.push(ValidationError::IndexOutOfRange {
+                    cluster_index,
+                    point_index,
+                });
+            }
+            if !(S::zero()..=S::one()).contains(&label) {
+                errors.push(ValidationError::LabelOutOfRange {
+                    cluster_index,
+                    point_index,
+                });
+            } else if assignment.category == Category::Noise && label != S::one() {
+                errors.push(ValidationError::NoiseLabelNotOne {
+                    cluster_index,
+                    point_index,
+                });
+            }
+            match labels.get(&point_index) {
+                Some(&other_label) if other_label != label => {
+                    errors.push(ValidationError::ConflictingLabels {
+                        cluster_index,
+                        point_index,
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    labels.insert(point_index, label);
+                }
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
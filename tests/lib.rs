@@ -12,21 +12,45 @@
 //! - core fuzziness is controlled by pts_min and pts_max
 //! - border-fuzziness is controlled by eps_min and eps_max
 extern crate fuzzy_dbscan;
+extern crate proptest;
+extern crate serde_json;
 extern crate utils;
 
+use fuzzy_dbscan::membership::*;
 use fuzzy_dbscan::*;
+use proptest::prelude::*;
 use utils::*;
 
 #[macro_export]
 macro_rules! assert_any {
     ( $clusters:ident, $pred:expr, $res:expr ) => {
         assert!(
-            $clusters.iter().all(|ref c| c.iter().any($pred)) == $res,
+            $clusters.iter().all(|c| c.iter().any($pred)) == $res,
             concat!("(", stringify!($pred), ") != ", stringify!($res))
         );
     };
 }
 
+// A point on a line, for hand-crafted test cases.
+#[derive(Clone)]
+struct Point1D(f64);
+
+impl MetricSpace for Point1D {
+    fn distance(&self, other: &Self) -> f64 {
+        (other.0 - self.0).abs()
+    }
+}
+
+impl Coordinates<1> for Point1D {
+    fn coordinates(&self) -> [f64; 1] {
+        [self.0]
+    }
+}
+
+fn line(xs: &[f64]) -> Vec<Point1D> {
+    xs.iter().map(|&x| Point1D(x)).collect()
+}
+
 const BASE_N: usize = 400;
 const BASE_R: f64 = 10.0;
 
@@ -42,6 +66,35 @@ fn mixed_gaussian() -> Vec<Point> {
     ]
 }
 
+fn bimodal_gaussian() -> Vec<Point> {
+    flat_vec![
+        gaussian_circle(BASE_N / 4, 0.0, 0.0, BASE_R),
+        gaussian_circle(BASE_N / 4, BASE_R * 5.0, 0.0, BASE_R),
+    ]
+}
+
+// Sorts the assignments of each cluster and rounds labels to ignore summation order.
+fn normalized(clusters: impl Into<Vec<Cluster>>) -> Vec<Vec<(usize, String, f64)>> {
+    clusters
+        .into()
+        .into_iter()
+        .map(|cluster| {
+            let mut cluster: Vec<_> = cluster
+                .into_iter()
+                .map(|a| {
+                    (
+                        a.index,
+                        format!("{:?}", a.category),
+                        (a.label * 1e9).round(),
+                    )
+                })
+                .collect();
+            cluster.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            cluster
+        })
+        .collect()
+}
+
 // FuzzyDBSCAN should reduce to DBSCAN (eps_min = eps_max, pts_min = pts_max), i.e.,
 // clusters should have crisp cores only.
 #[test]
@@ -53,12 +106,210 @@ fn reduce_to_dbscan() {
         eps_max: BASE_R,
         pts_min: 1.0,
         pts_max: 1.0,
+        ..Default::default()
     };
-    let clusters = fuzzy_dbscan.cluster(&points);
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
     dump_svg("reduce_to_dbscan", &points, &clusters);
     assert_eq!(clusters.len(), 1);
-    assert_any!(clusters, |ref a| a.label != 1.0, false);
-    assert_any!(clusters, |ref a| a.category != Category::Core, false);
+    assert_any!(clusters, |a| a.label != 1.0, false);
+    assert_any!(clusters, |a| a.category != Category::Core, false);
+    assert!(defuzzify(points.len(), &clusters)
+        .iter()
+        .all(|&cluster_id| cluster_id == Some(0)));
+    let stats = label_statistics(&clusters[0], Category::Core);
+    assert_eq!((stats.mean, stats.std), (1.0, 0.0));
+    assert_eq!((stats.min, stats.max), (1.0, 1.0));
+    assert_eq!(stats.count, clusters[0].len());
+    let stats = label_statistics(&clusters[0], Category::Border);
+    assert_eq!(stats.count, 0);
+    assert!(stats.mean.is_nan());
+}
+
+// Crisp inner densities should reduce to DBSCAN, but ignore neighbors beyond eps_min.
+#[test]
+fn density_modes() {
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = |eps_min, density_mode| FuzzyDBSCAN {
+        eps_min,
+        eps_max: BASE_R / 2.0,
+        pts_min: 5.0,
+        pts_max: 5.0,
+        density_mode,
+        ..Default::default()
+    };
+    let dbscan = fuzzy_dbscan(BASE_R / 2.0, DensityMode::FuzzyWeighted)
+        .cluster(&points)
+        .unwrap();
+    let crisp_inner = fuzzy_dbscan(BASE_R / 2.0, DensityMode::CrispInner)
+        .cluster(&points)
+        .unwrap();
+    assert_eq!(normalized(crisp_inner), normalized(dbscan));
+    let line_points = line(&[0.0, 1.5, 3.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        density_mode: DensityMode::CrispInner,
+        ..Default::default()
+    };
+    assert_eq!(fuzzy_dbscan.compute_density(&line_points, 1), 1.0);
+    assert_eq!(fuzzy_dbscan.density_at(&Point1D(1.0), &line_points), 3.0);
+}
+
+// Excluding points from their own density should turn isolated points into noise.
+#[test]
+fn self_included_in_density() {
+    let points = unimodal_gaussian();
+    let fuzzy_dbscan = |self_included_in_density, pts_min| FuzzyDBSCAN {
+        eps_min: BASE_R / 1000.0,
+        eps_max: BASE_R / 1000.0,
+        pts_min,
+        pts_max: pts_min,
+        self_included_in_density,
+        ..Default::default()
+    };
+    let included = fuzzy_dbscan(true, 1.0).cluster(&points).unwrap();
+    assert!(query::noise_points(&included).is_empty());
+    let excluded = fuzzy_dbscan(false, 1.0).cluster(&points).unwrap();
+    assert!(query::noise_points(&excluded).len() > points.len() * 9 / 10);
+    // Excluding the point itself is equivalent to requiring one more point.
+    let fuzzy_dbscan = |self_included_in_density, pts_min| FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R,
+        pts_min,
+        pts_max: pts_min + 5.0,
+        self_included_in_density,
+        ..Default::default()
+    };
+    assert_eq!(
+        normalized(fuzzy_dbscan(false, 5.0).cluster(&points).unwrap()),
+        normalized(fuzzy_dbscan(true, 6.0).cluster(&points).unwrap())
+    );
+    assert_eq!(
+        fuzzy_dbscan(true, 5.0).compute_density(&points, 0),
+        1.0 + fuzzy_dbscan(false, 5.0).compute_density(&points, 0)
+    );
+}
+
+// Uniform circles and grids should form a single cluster, with borders only at grid corners.
+#[test]
+fn uniform_and_grid_points() {
+    let points = uniform_circle(BASE_N, 0.0, 0.0, BASE_R);
+    assert_eq!(points.len(), BASE_N);
+    assert!(points
+        .iter()
+        .all(|point| point.coordinates()[0].hypot(point.coordinates()[1]) <= BASE_R));
+    let clusters = FuzzyDBSCAN::with_mode(ClusteringMode::Crisp, BASE_R / 2.0, 5.0)
+        .cluster(&points)
+        .unwrap();
+    dump_svg("uniform_circle", &points, &clusters);
+    assert_eq!(clusters.len(), 1);
+    let points = grid_points(3, 4, 2.0);
+    assert_eq!(points.len(), 12);
+    let clusters = FuzzyDBSCAN::with_mode(ClusteringMode::Crisp, 2.0, 4.0)
+        .cluster(&points)
+        .unwrap();
+    let indices: Vec<usize> = (0..12).collect();
+    assert_eq!(query::cluster_indices(&clusters)[0], indices);
+    assert_eq!(query::border_points(&clusters), vec![0, 3, 8, 11]);
+}
+
+// Expanding the densest points first should not misclassify grid corners as noise.
+#[test]
+fn expansion_orders() {
+    let points = grid_points(3, 4, 2.0);
+    let clusters = |expansion_order| {
+        FuzzyDBSCAN {
+            expansion_order,
+            ..FuzzyDBSCAN::with_mode(ClusteringMode::Crisp, 2.0, 4.0)
+        }
+        .cluster(&points)
+        .unwrap()
+    };
+    let sequential = clusters(ExpansionOrder::Sequential);
+    let highest_density = clusters(ExpansionOrder::HighestDensity);
+    assert_eq!(query::noise_points(&sequential), vec![0]);
+    assert_eq!(highest_density.len(), 1);
+    assert!(query::noise_points(&highest_density).is_empty());
+    assert_eq!(
+        query::cluster_indices(&highest_density)[0],
+        query::cluster_indices(&sequential)[0]
+    );
+    assert_eq!(query::border_points(&highest_density), vec![0, 3, 8, 11]);
+}
+
+// Non-convex shapes should form one cluster each, even if they enclose other clusters.
+#[test]
+fn non_convex_shapes() {
+    let fuzzy_dbscan = FuzzyDBSCAN::with_mode(ClusteringMode::Crisp, 2.0, 5.0);
+    let points = ring(BASE_N, 0.0, 0.0, BASE_R * 0.8, BASE_R);
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    dump_svg("ring", &points, &clusters);
+    assert!(defuzzify(points.len(), &clusters)
+        .iter()
+        .all(|&cluster_id| cluster_id == Some(0)));
+    let points = flat_vec![
+        crescent(BASE_N, 0.0, 0.0, BASE_R * 1.5, BASE_R * 2.0),
+        gaussian_circle(BASE_N / 4, 0.0, BASE_R / 2.0, BASE_R / 2.0),
+    ];
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    dump_svg("crescent", &points, &clusters);
+    let assignments = defuzzify(points.len(), &clusters);
+    let (crescent, blob) = assignments.split_at(BASE_N);
+    assert!(crescent.iter().all(|&cluster_id| cluster_id == Some(0)));
+    // Outliers of the blob may be noise, but must not join the crescent.
+    assert!(blob.iter().all(|&cluster_id| cluster_id != Some(0)));
+    assert!(blob.contains(&Some(1)));
+}
+
+// Interleaved spirals should be separated into one cluster each.
+#[test]
+fn spirals() {
+    let (a, b) = two_spirals(BASE_N);
+    let points = flat_vec![a, b,];
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 1.5,
+        pts_min: 3.0,
+        pts_max: 5.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    dump_svg("two_spirals", &points, &clusters);
+    assert_eq!(clusters.cluster_count(), 2);
+    let assignments = defuzzify(points.len(), &clusters);
+    let (a, b) = assignments.split_at(BASE_N);
+    // Outliers may be noise, but must not join the other spiral.
+    assert!(a.iter().all(|&cluster_id| cluster_id != Some(1)));
+    assert!(b.iter().all(|&cluster_id| cluster_id != Some(0)));
+    assert_eq!(spiral(BASE_N, 3.0, 0.0).len(), BASE_N);
+}
+
+// Modes should set the parameters of the corresponding reduction and be detected from them.
+#[test]
+fn clustering_modes() {
+    let modes = [
+        ClusteringMode::Crisp,
+        ClusteringMode::FuzzyCore,
+        ClusteringMode::FuzzyBorder,
+        ClusteringMode::Full,
+    ];
+    for mode in modes {
+        let fuzzy_dbscan = FuzzyDBSCAN::with_mode(mode, BASE_R, 4.0);
+        assert_eq!(fuzzy_dbscan.mode(), mode);
+        assert_eq!(fuzzy_dbscan.validate(), Ok(()));
+        assert_eq!(fuzzy_dbscan.eps_max, BASE_R);
+        assert_eq!(fuzzy_dbscan.pts_max, 4.0);
+    }
+    let fuzzy_dbscan = FuzzyDBSCAN::with_mode(ClusteringMode::Full, BASE_R, 4.0);
+    assert_eq!(fuzzy_dbscan.eps_min, BASE_R / 2.0);
+    assert_eq!(fuzzy_dbscan.pts_min, 2.0);
+    let points = unimodal_gaussian();
+    let clusters = FuzzyDBSCAN::with_mode(ClusteringMode::Crisp, BASE_R, 1.0)
+        .cluster(&points)
+        .unwrap();
+    assert_any!(clusters, |a| a.label != 1.0, false);
 }
 
 // FuzzyDBSCAN should reduce to FuzzyCoreDBSCAN (eps_min = eps_max), i.e.,
@@ -73,17 +324,18 @@ fn reduce_to_fuzzy_core_dbscan() {
         eps_max: BASE_R,
         pts_min: 1.0,
         pts_max: BASE_N as f64,
+        ..Default::default()
     };
-    let clusters = fuzzy_dbscan.cluster(&points);
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
     dump_svg("reduce_to_fuzzy_core_dbscan", &points, &clusters);
     assert_eq!(clusters.len(), 1);
     assert_any!(
         clusters,
-        |ref a| a.category == Category::Core && a.label != 1.0,
+        |a| a.category == Category::Core && a.label != 1.0,
         true
     );
-    assert_any!(clusters, |ref a| a.category == Category::Border, false);
-    assert_any!(clusters, |ref a| a.category == Category::Noise, false);
+    assert_any!(clusters, |a| a.category == Category::Border, false);
+    assert_any!(clusters, |a| a.category == Category::Noise, false);
 }
 
 // FuzzyDBSCAN should reduce to FuzzyBorderDBSCAN (pts_min = pts_max), i.e.,
@@ -91,23 +343,52 @@ fn reduce_to_fuzzy_core_dbscan() {
 #[test]
 fn reduce_to_fuzzy_border_dbscan() {
     let points = unimodal_gaussian();
-    // Expect that crisp core points are close to 50% of the points within the
-    // maximum radius, thus less than 50% neighbourhood means border.
-    let fuzzy_dbscan = FuzzyDBSCAN {
-        eps_min: 1.0,
-        eps_max: BASE_R,
-        pts_min: (BASE_N / 2) as f64,
-        pts_max: (BASE_N / 2) as f64,
+    for border_label_mode in [BorderLabelMode::MinCore, BorderLabelMode::MaxCore] {
+        // Expect that crisp core points are close to 50% of the points within the
+        // maximum radius, thus less than 50% neighbourhood means border.
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min: 1.0,
+            eps_max: BASE_R,
+            pts_min: (BASE_N / 2) as f64,
+            pts_max: (BASE_N / 2) as f64,
+            border_label_mode,
+            ..Default::default()
+        };
+        let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+        if border_label_mode == BorderLabelMode::MinCore {
+            dump_svg("reduce_to_fuzzy_border_dbscan", &points, &clusters);
+        }
+        assert_eq!(clusters.len(), 1);
+        assert_any!(
+            clusters,
+            |a| a.category == Category::Core && a.label != 1.0,
+            false
+        );
+        assert_any!(clusters, |a| a.category == Category::Border, true);
+    }
+}
+
+// Border labels should be derived from the farthest or the closest core point.
+#[test]
+fn border_label_modes() {
+    let points = line(&[0.0, 0.5, 1.0, 2.25]);
+    let border_label = |border_label_mode| {
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min: 1.0,
+            eps_max: 2.0,
+            pts_min: 3.0,
+            pts_max: 3.0,
+            border_label_mode,
+            ..Default::default()
+        };
+        let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+        assert_eq!(clusters.len(), 1);
+        let border = clusters[0].iter().find(|a| a.index == 3).unwrap();
+        assert_eq!(border.category, Category::Border);
+        border.label
     };
-    let clusters = fuzzy_dbscan.cluster(&points);
-    dump_svg("reduce_to_fuzzy_border_dbscan", &points, &clusters);
-    assert_eq!(clusters.len(), 1);
-    assert_any!(
-        clusters,
-        |ref a| a.category == Category::Core && a.label != 1.0,
-        false
-    );
-    assert_any!(clusters, |ref a| a.category == Category::Border, true);
+    assert_eq!(border_label(BorderLabelMode::MinCore), 0.25);
+    assert_eq!(border_label(BorderLabelMode::MaxCore), 0.75);
 }
 
 // FuzzyDBSCAN should find varying fuzzy cores and borders.
@@ -122,21 +403,22 @@ fn full_fuzzy_dbscan() {
         eps_max: BASE_R / 3.0 * 2.0,
         pts_min: (BASE_N / 2) as f64 * 0.68,
         pts_max: (BASE_N / 2) as f64 * 0.95,
+        ..Default::default()
     };
-    let clusters = fuzzy_dbscan.cluster(&points);
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
     dump_svg("full_fuzzy_dbscan", &points, &clusters);
     assert_eq!(clusters.len(), 2);
     assert_any!(
         clusters,
-        |ref a| a.category == Category::Core && a.label != 1.0,
+        |a| a.category == Category::Core && a.label != 1.0,
         true
     );
     assert_any!(
         clusters,
-        |ref a| a.category == Category::Border && a.label != 1.0,
+        |a| a.category == Category::Border && a.label != 1.0,
         true
     );
-    assert_any!(clusters, |ref a| a.category == Category::Noise, false);
+    assert_any!(clusters, |a| a.category == Category::Noise, false);
 }
 
 // FuzzyDBSCAN should find noise.
@@ -148,9 +430,2691 @@ fn noise() {
         eps_max: BASE_R * 4.0,
         pts_min: BASE_N as f64 * 2.0,
         pts_max: BASE_N as f64 * 4.0,
+        ..Default::default()
     };
-    let clusters = fuzzy_dbscan.cluster(&points);
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
     dump_svg("noise", &points, &clusters);
     assert_eq!(clusters.len(), 1);
-    assert_any!(clusters, |ref a| a.category != Category::Noise, false);
+    assert_any!(clusters, |a| a.category != Category::Noise, false);
+    assert!(defuzzify(points.len(), &clusters)
+        .iter()
+        .all(|cluster_id| cluster_id.is_none()));
+}
+
+// The bounding box index should find exactly the same neighbors as brute force.
+#[test]
+fn bounding_box_index() {
+    use index::SpatialIndex;
+    let points = mixed_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 3.0,
+        eps_max: BASE_R / 3.0 * 2.0,
+        pts_min: (BASE_N / 2) as f64 * 0.68,
+        pts_max: (BASE_N / 2) as f64 * 0.95,
+        ..Default::default()
+    };
+    let brute_force: index::BruteForce = SpatialIndex::build(&points, fuzzy_dbscan.eps_max);
+    let bounding_box: index::BoundingBoxIndex<2> =
+        SpatialIndex::build(&points, fuzzy_dbscan.eps_max);
+    for point_index in 0..points.len() {
+        assert_eq!(
+            bounding_box.region_query_with_distances(&points, point_index),
+            brute_force.region_query_with_distances(&points, point_index)
+        );
+    }
+    let expected = normalized(
+        fuzzy_dbscan
+            .cluster_with_index::<index::BruteForce, _>(&points)
+            .unwrap(),
+    );
+    let actual = normalized(
+        fuzzy_dbscan
+            .cluster_with_index::<index::BoundingBoxIndex<2>, _>(&points)
+            .unwrap(),
+    );
+    assert_eq!(actual, expected);
+}
+
+// The grid index should find exactly the same neighbors as brute force.
+#[test]
+fn grid_index() {
+    use index::SpatialIndex;
+    let points = flat_vec![
+        mixed_gaussian(),
+        uniform_circle(BASE_N, 0.0, 0.0, BASE_R * 3.0),
+    ];
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 3.0,
+        eps_max: BASE_R / 3.0 * 2.0,
+        pts_min: (BASE_N / 2) as f64 * 0.68,
+        pts_max: (BASE_N / 2) as f64 * 0.95,
+        ..Default::default()
+    };
+    let brute_force: index::BruteForce = SpatialIndex::build(&points, fuzzy_dbscan.eps_max);
+    let grid: index::GridIndex = SpatialIndex::build(&points, fuzzy_dbscan.eps_max);
+    for point_index in 0..points.len() {
+        assert_eq!(
+            grid.region_query_with_distances(&points, point_index),
+            brute_force.region_query_with_distances(&points, point_index)
+        );
+    }
+    let expected = normalized(
+        fuzzy_dbscan
+            .cluster_with_index::<index::BruteForce, _>(&points)
+            .unwrap(),
+    );
+    let actual = normalized(
+        fuzzy_dbscan
+            .cluster_with_index::<index::GridIndex, _>(&points)
+            .unwrap(),
+    );
+    assert_eq!(actual, expected);
+}
+
+// FuzzyDBSCAN should find the same clusters using a KD-tree as with brute force.
+#[cfg(feature = "kiddo")]
+#[test]
+fn kd_tree_index() {
+    let points = mixed_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 3.0,
+        eps_max: BASE_R / 3.0 * 2.0,
+        pts_min: (BASE_N / 2) as f64 * 0.68,
+        pts_max: (BASE_N / 2) as f64 * 0.95,
+        ..Default::default()
+    };
+    let expected = normalized(
+        fuzzy_dbscan
+            .cluster_with_index::<index::BruteForce, _>(&points)
+            .unwrap(),
+    );
+    let actual = normalized(
+        fuzzy_dbscan
+            .cluster_with_index::<index::KdTree<2>, _>(&points)
+            .unwrap(),
+    );
+    assert_eq!(actual, expected);
+    // Single precision points should be supported as well.
+    let points: Vec<Point2D> = points
+        .iter()
+        .map(|point| {
+            let [x, y] = point.coordinates();
+            Point2D::new(x as f32, y as f32)
+        })
+        .collect();
+    let fuzzy_dbscan = FuzzyDBSCAN::<f32> {
+        eps_min: fuzzy_dbscan.eps_min as f32,
+        eps_max: fuzzy_dbscan.eps_max as f32,
+        pts_min: fuzzy_dbscan.pts_min as f32,
+        pts_max: fuzzy_dbscan.pts_max as f32,
+        ..Default::default()
+    };
+    let expected = fuzzy_dbscan
+        .cluster_with_index::<index::BruteForce<f32>, _>(&points)
+        .unwrap();
+    let actual = fuzzy_dbscan
+        .cluster_with_index::<index::KdTree<2, f32>, _>(&points)
+        .unwrap();
+    assert_eq!(actual, expected);
+}
+
+// The default parameters should be valid and cluster standardized data.
+#[test]
+fn default_parameters() {
+    let fuzzy_dbscan = FuzzyDBSCAN::new();
+    assert_eq!(fuzzy_dbscan.validate(), Ok(()));
+    assert_eq!(fuzzy_dbscan.mode(), ClusteringMode::Full);
+    assert_eq!((fuzzy_dbscan.eps_min, fuzzy_dbscan.eps_max), (0.5, 1.0));
+    assert_eq!((fuzzy_dbscan.pts_min, fuzzy_dbscan.pts_max), (2.0, 5.0));
+    let points = flat_vec![
+        gaussian_circle(BASE_N / 4, 0.0, 0.0, 3.0),
+        gaussian_circle(BASE_N / 4, 10.0, 0.0, 3.0),
+    ];
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let assignments = defuzzify(points.len(), &clusters);
+    assert_eq!(assignments.iter().flatten().max(), Some(&1));
+}
+
+// The builder should reject invalid parameters.
+#[test]
+fn builder_validation() {
+    let builder = || {
+        FuzzyDBSCAN::builder()
+            .eps_min(1.0)
+            .eps_max(2.0)
+            .pts_min(1.0)
+            .pts_max(2.0)
+    };
+    assert!(builder().build().is_ok());
+    assert_eq!(
+        builder().eps_min(3.0).build().err(),
+        Some(InvalidParametersError::EpsRangeInverted)
+    );
+    assert_eq!(
+        builder().pts_min(3.0).build().err(),
+        Some(InvalidParametersError::PtsRangeInverted)
+    );
+    assert_eq!(
+        builder().eps_min(-1.0).build().err(),
+        Some(InvalidParametersError::NegativeEps)
+    );
+    assert_eq!(
+        builder().pts_min(-1.0).build().err(),
+        Some(InvalidParametersError::NegativePts)
+    );
+    assert_eq!(
+        builder().pts_max(f64::NAN).build().err(),
+        Some(InvalidParametersError::NaNParameter)
+    );
+    assert_eq!(
+        FuzzyDBSCAN::<f64>::builder().build().err(),
+        Some(InvalidParametersError::NaNParameter)
+    );
+    assert_eq!(
+        builder().eps_max(f64::INFINITY).build().err(),
+        Some(InvalidParametersError::InfiniteParameter)
+    );
+    assert_eq!(
+        builder().pts_max(f64::INFINITY).build().err(),
+        Some(InvalidParametersError::InfiniteParameter)
+    );
+    assert_eq!(
+        builder().core_threshold(1.5).build().err(),
+        Some(InvalidParametersError::ThresholdOutOfRange)
+    );
+}
+
+// Cores at or below the core threshold should not seed micro-clusters, and border points below
+// the border threshold should become noise.
+#[test]
+fn category_thresholds() {
+    let points = line(&[0.0, 0.5, 1.0, 1.5, 2.0, 10.0, 10.9, 11.8]);
+    let fuzzy_dbscan = |core_threshold, border_threshold| FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 1.0,
+        pts_min: 2.0,
+        pts_max: 5.0,
+        core_threshold,
+        border_threshold,
+        ..Default::default()
+    };
+    let is_noise = |clusters: &ClusterResult, indices: &[usize]| {
+        indices.iter().all(|&index| clusters.is_noise(index))
+    };
+    let clusters = fuzzy_dbscan(0.0, 0.0).cluster(&points).unwrap();
+    assert_eq!(clusters.cluster_count(), 2);
+    assert_eq!(query::core_points(&clusters), [0, 1, 2, 3, 4, 6]);
+    let clusters = fuzzy_dbscan(0.5, 0.0).cluster(&points).unwrap();
+    assert_eq!(clusters.cluster_count(), 1);
+    assert!(is_noise(&clusters, &[5, 6, 7]));
+    assert_eq!(query::border_points(&clusters), [0, 4]);
+    let clusters = fuzzy_dbscan(0.5, 0.9).cluster(&points).unwrap();
+    assert_eq!(clusters.cluster_count(), 1);
+    assert!(is_noise(&clusters, &[0, 4, 5, 6, 7]));
+    assert!(query::border_points(&clusters).is_empty());
+    let lazy_clusters: Vec<Cluster> = fuzzy_dbscan(0.5, 0.9)
+        .cluster_iter(&points)
+        .unwrap()
+        .collect();
+    assert_eq!(normalized(lazy_clusters), normalized(clusters.to_vec()));
+    assert!(fuzzy_dbscan(0.0, 0.0).dry_run(&points).unwrap()[6] > 0.0);
+    let labels = fuzzy_dbscan(0.5, 0.0).dry_run(&points).unwrap();
+    assert_eq!(labels[5..], [0.0; 3]);
+    let mut state = ClusterState::new();
+    fuzzy_dbscan(0.5, 0.9).update(&mut state, &points).unwrap();
+    let updated: Vec<Cluster> = state.clusters().iter().map(|(_, c)| c.clone()).collect();
+    assert_eq!(
+        normalized(updated),
+        normalized(clusters.real_clusters().to_vec())
+    );
+    assert_eq!(state.noise().len(), 5);
+    assert_eq!(
+        fuzzy_dbscan(-0.1, 0.0).cluster(&points).err(),
+        Some(FuzzyDBSCANError::InvalidThreshold)
+    );
+}
+
+// FuzzyDBSCAN should work with single precision distances.
+#[test]
+fn single_precision() {
+    struct Point32 {
+        x: f32,
+    }
+    impl MetricSpace<f32> for Point32 {
+        fn distance(&self, other: &Self) -> f32 {
+            (other.x - self.x).abs()
+        }
+    }
+    let points: Vec<Point32> = [0.0, 1.0, 2.0, 100.0]
+        .iter()
+        .map(|&x| Point32 { x })
+        .collect();
+    let fuzzy_dbscan = FuzzyDBSCAN::builder()
+        .eps_min(1.0f32)
+        .eps_max(1.0)
+        .pts_min(2.0)
+        .pts_max(2.0)
+        .build()
+        .unwrap();
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(clusters.len(), 2);
+    assert!(clusters[0].iter().all(|a| a.index < 3 && a.label == 1.0));
+    assert_eq!(clusters[1][0].index, 3);
+    assert_eq!(clusters[1][0].category, Category::Noise);
+}
+
+// FuzzyDBSCAN should find the same clusters using a precomputed distance matrix.
+#[test]
+fn distance_matrix() {
+    let points = mixed_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 3.0,
+        eps_max: BASE_R / 3.0 * 2.0,
+        pts_min: (BASE_N / 2) as f64 * 0.68,
+        pts_max: (BASE_N / 2) as f64 * 0.95,
+        ..Default::default()
+    };
+    let matrix = DistanceMatrix::from_points(&points, Point::distance);
+    assert_eq!(matrix.len(), points.len());
+    assert_eq!(matrix[1][2], points[1].distance(&points[2]));
+    assert_eq!(
+        normalized(fuzzy_dbscan.cluster_with_matrix(&matrix).unwrap()),
+        normalized(fuzzy_dbscan.cluster(&points).unwrap())
+    );
+}
+
+// FuzzyDBSCAN should find the same clusters using a distance function.
+#[test]
+fn distance_fn() {
+    let xs = [0.0, 1.0, 2.75, 4.5, 5.5, 10.0];
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let expected = normalized(fuzzy_dbscan.cluster(&line(&xs)).unwrap());
+    let distance = |a: &f64, b: &f64| (b - a).abs();
+    let clusters = fuzzy_dbscan.cluster_with_fn(&xs, distance).unwrap();
+    assert_eq!(clusters.cluster_count(), 2);
+    assert_eq!(normalized(clusters), expected);
+    #[cfg(feature = "rayon")]
+    assert_eq!(
+        normalized(
+            fuzzy_dbscan
+                .cluster_with_fn_parallel(&xs, distance)
+                .unwrap()
+        ),
+        expected
+    );
+}
+
+// The clusterer should report new, growing and merging clusters.
+#[test]
+fn incremental_clusterer() {
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 1.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let mut clusterer = fuzzy_dbscan
+        .into_clusterer(line(&[0.0, 1.0, 5.0, 6.0]))
+        .unwrap();
+    assert_eq!(clusterer.clusters().len(), 2);
+    assert!(clusterer.add_point(Point1D(10.0)).is_empty());
+    assert_eq!(clusterer.noise().len(), 1);
+    match clusterer.add_point(Point1D(2.0)).as_slice() {
+        [ClusterUpdate::PointAddedToCluster {
+            cluster_id: 0,
+            assignment,
+        }] => assert_eq!(assignment.index, 5),
+        updates => panic!("unexpected {:?}", updates),
+    }
+    assert_eq!(clusterer.add_point(Point1D(3.0)).len(), 1);
+    match clusterer.add_point(Point1D(4.0)).as_slice() {
+        [ClusterUpdate::ClustersWereMerged { ids }, ClusterUpdate::PointAddedToCluster { cluster_id: 0, .. }] =>
+        {
+            assert_eq!(ids, &[0, 1])
+        }
+        updates => panic!("unexpected {:?}", updates),
+    }
+    match clusterer.add_point(Point1D(11.0)).as_slice() {
+        [ClusterUpdate::NewClusterCreated { cluster_id: 2 }, ClusterUpdate::PointAddedToCluster { cluster_id: 2, .. }] =>
+            {}
+        updates => panic!("unexpected {:?}", updates),
+    }
+    let ids: Vec<usize> = clusterer.clusters().iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids, [0, 2]);
+    assert!(clusterer.noise().is_empty());
+}
+
+// Updating a state should report changes and agree with clustering all points at once.
+#[test]
+fn update_state() {
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 1.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let mut state = ClusterState::new();
+    let updates = fuzzy_dbscan
+        .update(&mut state, &line(&[0.0, 1.0, 5.0, 6.0, 10.0]))
+        .unwrap();
+    assert_eq!(updates.len(), 6);
+    assert_eq!(state.clusters().len(), 2);
+    assert_eq!(state.noise().len(), 1);
+    match fuzzy_dbscan
+        .update(&mut state, &[Point1D(2.0)])
+        .unwrap()
+        .as_slice()
+    {
+        [ClusterUpdate::ClusterExpanded { cluster_id: 0 }, ClusterUpdate::PointAddedToCluster {
+            cluster_id: 0,
+            assignment,
+        }] => assert_eq!(assignment.index, 5),
+        updates => panic!("unexpected {:?}", updates),
+    }
+    match fuzzy_dbscan
+        .update(&mut state, &line(&[3.0, 4.0]))
+        .unwrap()
+        .as_slice()
+    {
+        [ClusterUpdate::ClustersWereMerged { ids }, ClusterUpdate::PointAddedToCluster { cluster_id: 0, .. }, ClusterUpdate::PointAddedToCluster { cluster_id: 0, .. }] =>
+        {
+            assert_eq!(ids, &[0, 1])
+        }
+        updates => panic!("unexpected {:?}", updates),
+    }
+    match fuzzy_dbscan
+        .update(&mut state, &[Point1D(11.0)])
+        .unwrap()
+        .as_slice()
+    {
+        [ClusterUpdate::NewClusterCreated { cluster_id: 2 }, ClusterUpdate::PointAddedToCluster { cluster_id: 2, .. }] =>
+            {}
+        updates => panic!("unexpected {:?}", updates),
+    }
+    assert!(state.noise().is_empty());
+
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R,
+        pts_min: 3.0,
+        pts_max: 10.0,
+        ..Default::default()
+    };
+    let mut state = ClusterState::new();
+    for chunk in points.chunks(37) {
+        fuzzy_dbscan.update(&mut state, chunk).unwrap();
+    }
+    let sorted = |mut clusters: Vec<Vec<(usize, String, f64)>>| {
+        clusters.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        clusters
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let updated: Vec<Cluster> = state.clusters().iter().map(|(_, c)| c.clone()).collect();
+    assert!(updated.len() > 1);
+    assert_eq!(
+        sorted(normalized(updated)),
+        sorted(normalized(clusters.real_clusters().to_vec()))
+    );
+    // Clustering all points at once may also classify border points as noise.
+    let noise: Vec<usize> = (0..points.len())
+        .filter(|&index| clusters.point_cluster(index).is_none())
+        .collect();
+    let updated_noise: Vec<usize> = state.noise().iter().map(|a| a.index).collect();
+    assert_eq!(updated_noise, noise);
+}
+
+// Clustering results should be cloneable and comparable.
+#[test]
+fn clone_clusters() {
+    let points = mixed_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 3.0,
+        eps_max: BASE_R / 3.0 * 2.0,
+        pts_min: (BASE_N / 2) as f64 * 0.68,
+        pts_max: (BASE_N / 2) as f64 * 0.95,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let cloned = clusters.clone();
+    assert_eq!(cloned, clusters);
+}
+
+// Summaries should count the categories of each cluster.
+#[test]
+fn summaries() {
+    let points = unimodal_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R,
+        eps_max: BASE_R,
+        pts_min: 1.0,
+        pts_max: 1.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let summaries = summarize(&clusters);
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].core_count, clusters[0].len());
+    assert_eq!(summaries[0].border_count, 0);
+    assert_eq!(summaries[0].noise_count, 0);
+    assert_eq!(summaries[0].mean_label, 1.0);
+    assert!(summaries[0].to_string().starts_with("cluster    0 | core "));
+    assert_eq!(Category::Border.to_string(), "Border");
+}
+
+// Clustering should fail for invalid parameters and empty input.
+#[test]
+fn cluster_errors() {
+    let points = line(&[0.0, 1.0]);
+    let cluster = |eps_min, eps_max, pts_min, pts_max| {
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min,
+            eps_max,
+            pts_min,
+            pts_max,
+            ..Default::default()
+        };
+        fuzzy_dbscan.cluster(&points).err()
+    };
+    assert_eq!(cluster(1.0, 2.0, 1.0, 2.0), None);
+    assert_eq!(
+        cluster(2.0, 1.0, 1.0, 2.0),
+        Some(FuzzyDBSCANError::InvalidEpsRange)
+    );
+    assert_eq!(
+        cluster(1.0, 2.0, 2.0, 1.0),
+        Some(FuzzyDBSCANError::InvalidPtsRange)
+    );
+    assert_eq!(
+        cluster(f64::NAN, 2.0, 1.0, 2.0),
+        Some(FuzzyDBSCANError::EpsNotFinite)
+    );
+    assert_eq!(
+        cluster(1.0, 2.0, 1.0, f64::INFINITY),
+        Some(FuzzyDBSCANError::PtsNotFinite)
+    );
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 1.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    assert_eq!(
+        fuzzy_dbscan.cluster::<Point1D>(&[]).err(),
+        Some(FuzzyDBSCANError::EmptyInput)
+    );
+}
+
+// The flat output should contain one assignment per point.
+#[test]
+fn flat_assignments() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let flat = fuzzy_dbscan.cluster_flat(&points).unwrap();
+    assert_eq!(flat.len(), points.len());
+    assert_eq!(flat[0].cluster_id, Some(0));
+    assert_eq!(flat[0].category, Category::Core);
+    // The point in the middle is a border point of both clusters.
+    assert_eq!(flat[2].category, Category::Border);
+    assert_eq!(flat[2].cluster_id, Some(0));
+    assert_eq!(flat[2].label, 0.25);
+    assert_eq!(flat[4].cluster_id, Some(1));
+    assert_eq!(flat[5].cluster_id, None);
+    assert_eq!(flat[5].category, Category::Noise);
+}
+
+// Clusters smaller than the minimum cluster size should become noise.
+#[test]
+fn min_cluster_size() {
+    let points = line(&[0.0, 1.0, 2.0, 5.0, 6.0, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 1.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        min_cluster_size: 3,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(clusters.len(), 2);
+    assert!(clusters[0].iter().all(|a| a.index < 3));
+    let mut noise: Vec<usize> = clusters[1]
+        .iter()
+        .filter(|a| a.category == Category::Noise && a.label == 1.0)
+        .map(|a| a.index)
+        .collect();
+    noise.sort_unstable();
+    assert_eq!(noise, [3, 4, 5]);
+    // If all clusters are too small, only noise remains.
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        min_cluster_size: 10,
+        ..fuzzy_dbscan
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0].len(), points.len());
+}
+
+// Border points shared with a kept cluster should not become noise when a small cluster is removed.
+#[test]
+fn min_cluster_size_shared_border() {
+    let points = line(&[0.0, 0.1, 0.2, 0.3, 1.25, 2.2, 2.3, 2.4]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 1.0,
+        pts_min: 4.0,
+        pts_max: 4.0,
+        min_cluster_size: 5,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(clusters.cluster_count(), 1);
+    assert!(clusters[0].iter().any(|a| a.index == 4));
+    let mut noise: Vec<usize> = clusters.noise().unwrap().iter().map(|a| a.index).collect();
+    noise.sort_unstable();
+    assert_eq!(noise, [5, 6, 7]);
+    let lazy_clusters: Vec<Cluster> = fuzzy_dbscan.cluster_iter(&points).unwrap().collect();
+    assert_eq!(normalized(lazy_clusters), normalized(clusters.to_vec()));
+}
+
+// Low-weight neighbors should not suffice to form a cluster.
+#[test]
+fn weighted_density() {
+    let points = line(&[0.0, 0.1, 0.2, 0.3, 0.4]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 1.0,
+        pts_min: 4.0,
+        pts_max: 4.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster_weighted(&points, &[1.0; 5]).unwrap();
+    assert_any!(clusters, |a| a.category != Category::Core, false);
+    let clusters = fuzzy_dbscan.cluster_weighted(&points, &[0.1; 5]).unwrap();
+    assert_eq!(clusters.len(), 1);
+    assert_any!(clusters, |a| a.category != Category::Noise, false);
+    assert_eq!(
+        fuzzy_dbscan.cluster_weighted(&points, &[1.0; 4]).err(),
+        Some(FuzzyDBSCANError::WeightsLengthMismatch)
+    );
+}
+
+// The silhouette score should improve when the parameters match the true clusters.
+#[test]
+fn silhouette() {
+    let points = bimodal_gaussian();
+    let score = |eps: f64| {
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min: eps,
+            eps_max: eps,
+            pts_min: 5.0,
+            pts_max: 5.0,
+            ..Default::default()
+        };
+        let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+        metrics::silhouette_score(&points, &clusters)
+    };
+    let poor = score(BASE_R * 5.0);
+    let good = score(BASE_R / 2.0);
+    assert_eq!(poor, 0.0);
+    assert!(good > 0.8, "{}", good);
+    let noise = [vec![Assignment {
+        index: 0,
+        cluster_id: None,
+        label: 1.0,
+        category: Category::Noise,
+    }]];
+    assert!(metrics::silhouette_score(&points[..1], &noise).is_nan());
+}
+
+// The Davies-Bouldin index should decrease when clusters are further apart.
+#[test]
+fn davies_bouldin() {
+    let index = |separation: f64| {
+        let points = flat_vec![
+            gaussian_circle(BASE_N / 4, 0.0, 0.0, BASE_R),
+            gaussian_circle(BASE_N / 4, separation, 0.0, BASE_R),
+        ];
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min: BASE_R / 2.0,
+            eps_max: BASE_R / 2.0,
+            pts_min: 5.0,
+            pts_max: 5.0,
+            ..Default::default()
+        };
+        let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+        assert_eq!(clusters.len(), 2);
+        metrics::davies_bouldin_index(&points, &clusters)
+    };
+    let near = index(BASE_R * 3.0);
+    let far = index(BASE_R * 6.0);
+    assert!(far < near, "{} < {}", far, near);
+}
+
+// Entropies should be zero for crisp assignments and grow with ambiguity.
+#[test]
+fn assignment_entropy() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(metrics::assignment_entropy(0, &clusters), 0.0);
+    assert_eq!(metrics::assignment_entropy(5, &clusters), 0.0);
+    // The shared border point has equal labels in both clusters.
+    let entropy = metrics::assignment_entropy(2, &clusters);
+    assert!((entropy - 2.0f64.ln()).abs() < 1e-12, "{}", entropy);
+    // The noise point is left out.
+    let mean = metrics::mean_entropy(&clusters, points.len());
+    assert!((mean - 2.0f64.ln() / 5.0).abs() < 1e-12, "{}", mean);
+    let border = |cluster_id: usize, label: f64| Assignment {
+        index: 0,
+        cluster_id: Some(cluster_id),
+        label,
+        category: Category::Border,
+    };
+    let skewed = [vec![border(0, 0.3)], vec![border(1, 0.7)]];
+    assert!(metrics::assignment_entropy(0, &skewed) < entropy);
+    // Noise assignments are no outcome of their own.
+    let noise = Assignment {
+        cluster_id: None,
+        category: Category::Noise,
+        ..border(0, 0.5)
+    };
+    let partly_noise = [vec![border(0, 0.5)], vec![noise.clone()]];
+    assert_eq!(metrics::assignment_entropy(0, &partly_noise), 0.0);
+    assert_eq!(metrics::mean_entropy(&partly_noise, 1), 0.0);
+    assert!(metrics::mean_entropy(&[vec![noise]], 1).is_nan());
+    assert!(metrics::mean_entropy::<f64>(&[], 0).is_nan());
+}
+
+// Nearby clusters should merge at a smaller eps_max than distant ones.
+#[test]
+fn persistence() {
+    let points = line(&[0.0, 1.0, 2.0, 5.0, 6.0, 7.0, 20.0, 21.0, 22.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 4.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let persistence = metrics::cluster_persistence(&points, &fuzzy_dbscan, 4).unwrap();
+    assert_eq!(persistence, [1.0, 1.0, 3.0]);
+    let persistence = metrics::cluster_persistence(&points, &fuzzy_dbscan, 1).unwrap();
+    assert_eq!(persistence, [0.0, 0.0, 0.0]);
+    assert!(metrics::cluster_persistence(&points, &fuzzy_dbscan, 0)
+        .unwrap()
+        .is_empty());
+    // The middle cluster only appears at eps_max = 2 and merges with the right one at 3.
+    let points = line(&[0.0, 1.0, 2.0, 10.0, 11.5, 13.0, 15.5, 16.5, 17.5]);
+    let persistence = metrics::cluster_persistence(&points, &fuzzy_dbscan, 4).unwrap();
+    assert_eq!(persistence, [3.0, 1.0, 0.0]);
+}
+
+// The DBCV index should approach one for well-separated clusters.
+#[test]
+fn dbcv() {
+    let index = |separation: f64| {
+        let points = flat_vec![
+            gaussian_circle(BASE_N / 4, 0.0, 0.0, BASE_R),
+            gaussian_circle(BASE_N / 4, separation, 0.0, BASE_R),
+        ];
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min: BASE_R / 2.0,
+            eps_max: BASE_R / 2.0,
+            pts_min: 5.0,
+            pts_max: 5.0,
+            ..Default::default()
+        };
+        let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+        assert_eq!(clusters.len(), 2);
+        metrics::dbcv(&points, &clusters, 5)
+    };
+    let near = index(BASE_R * 3.0);
+    let far = index(BASE_R * 20.0);
+    assert!(near < far, "{} < {}", near, far);
+    assert!(far > 0.9, "{}", far);
+    let points = line(&[0.0, 1.0]);
+    let cluster = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 1.0,
+        pts_min: 1.0,
+        pts_max: 1.0,
+        ..Default::default()
+    };
+    assert!(metrics::dbcv(&points, &cluster.cluster(&points).unwrap(), 5).is_nan());
+    // NaN distances should not panic.
+    let points = line(&[0.0, 0.5, 1.0, 10.0, 10.5, 11.0]);
+    let clusters = cluster.cluster(&points).unwrap();
+    let points = line(&[0.0, f64::NAN, 1.0, 10.0, 10.5, 11.0]);
+    metrics::dbcv(&points, &clusters, 2);
+}
+
+// Isocontours should trace the region in which the label reaches the threshold.
+#[test]
+fn isocontour() {
+    let fuzzy_dbscan = FuzzyDBSCAN::<f32> {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 1.0,
+        pts_max: 1.0,
+        ..Default::default()
+    };
+    let points = [Point2D::new(0.0, 0.0), Point2D::new(10.0, 0.0)];
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    // The label decreases linearly from eps_min to eps_max, thus the contour is a circle.
+    let polygon = fuzzy_dbscan.isocontour(&points, &clusters[0], 0.5, 0.1);
+    assert!(polygon.len() > 50, "{}", polygon.len());
+    for &(x, y) in &polygon {
+        let radius = (x * x + y * y).sqrt();
+        assert!((radius - 1.5).abs() < 0.01, "{}", radius);
+    }
+    let area = (0..polygon.len()).fold(0.0, |area, i| {
+        let ((x_a, y_a), (x_b, y_b)) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+        area + (x_a * y_b - x_b * y_a) / 2.0
+    });
+    let expected = std::f32::consts::PI * 1.5 * 1.5;
+    assert!((area.abs() - expected).abs() < expected * 0.01, "{}", area);
+    assert!(fuzzy_dbscan
+        .isocontour(&points, &clusters[0], 0.0, 0.1)
+        .is_empty());
+    // Tiny resolutions should not allocate a huge grid.
+    for resolution in [1e-6, f32::MIN_POSITIVE] {
+        assert!(fuzzy_dbscan
+            .isocontour(&points, &clusters[0], 0.5, resolution)
+            .is_empty());
+    }
+}
+
+// Points should map to all clusters they belong to.
+#[test]
+fn point_to_clusters() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let memberships = query::point_to_clusters(&clusters);
+    assert_eq!(memberships.len(), 5);
+    assert!(!memberships.contains_key(&5));
+    let mut border = memberships[&2].clone();
+    border.sort_by_key(|&(cluster_index, _, _)| cluster_index);
+    assert_eq!(
+        border,
+        vec![(0, 0.25, Category::Border), (1, 0.25, Category::Border)]
+    );
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R / 2.0,
+        pts_min: 5.0,
+        pts_max: 5.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let memberships = query::point_to_clusters(&clusters);
+    assert_eq!(
+        memberships.len() + clusters.noise().map_or(0, Vec::len),
+        points.len()
+    );
+    assert!(memberships
+        .values()
+        .all(|memberships| memberships.len() == 1 && memberships[0].1 == 1.0));
+}
+
+// Clustering results should satisfy all invariants, and violations should be reported.
+#[test]
+fn validate_clusters() {
+    let points = mixed_gaussian();
+    for border_label_mode in [BorderLabelMode::MinCore, BorderLabelMode::MaxCore] {
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min: BASE_R / 16.0,
+            eps_max: BASE_R / 8.0,
+            pts_min: 3.0,
+            pts_max: 5.0,
+            border_label_mode,
+            ..Default::default()
+        };
+        let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+        assert_eq!(
+            fuzzy_dbscan::validate_clusters(points.len(), &clusters),
+            Ok(())
+        );
+    }
+    let assignment = |index: usize, label: f64, category: Category| Assignment {
+        index,
+        cluster_id: None,
+        label,
+        category,
+    };
+    let clusters = vec![
+        vec![
+            assignment(0, 1.0, Category::Core),
+            assignment(3, 0.5, Category::Border),
+            assignment(1, f64::MAX, Category::Border),
+            assignment(0, 0.5, Category::Border),
+        ],
+        vec![],
+        vec![assignment(2, 0.5, Category::Noise)],
+    ];
+    assert_eq!(
+        fuzzy_dbscan::validate_clusters(3, &clusters),
+        Err(vec![
+            ValidationError::IndexOutOfRange {
+                cluster_index: 0,
+                point_index: 3,
+            },
+            ValidationError::LabelOutOfRange {
+                cluster_index: 0,
+                point_index: 1,
+            },
+            ValidationError::ConflictingLabels {
+                cluster_index: 0,
+                point_index: 0,
+            },
+            ValidationError::EmptyCluster { cluster_index: 1 },
+            ValidationError::NoiseLabelNotOne {
+                cluster_index: 2,
+                point_index: 2,
+            },
+        ])
+    );
+}
+
+// Memory estimates should grow linearly with the number of points and neighbors.
+#[test]
+fn memory_estimate() {
+    let estimate = FuzzyDBSCAN::<f64>::default().estimate_memory_usage(1000, 10);
+    assert_eq!(estimate.visited_bytes, 1000 * 10);
+    // Each neighbor is an index, a distance, and a label.
+    assert_eq!(
+        FuzzyDBSCAN::<f64>::default()
+            .estimate_memory_usage(1000, 11)
+            .neighbor_sets_bytes,
+        estimate.neighbor_sets_bytes + 1000 * std::mem::size_of::<(usize, f64, f64)>()
+    );
+    assert_eq!(
+        estimate.total_bytes,
+        estimate.visited_bytes + estimate.neighbor_sets_bytes + estimate.cluster_output_bytes
+    );
+    let doubled = FuzzyDBSCAN::<f64>::default().estimate_memory_usage(2000, 10);
+    assert_eq!(doubled.total_bytes, 2 * estimate.total_bytes);
+    let single = FuzzyDBSCAN::<f32>::default().estimate_memory_usage(1000, 10);
+    assert!(single.total_bytes < estimate.total_bytes);
+}
+
+// Compressed clusters should restore all assignments with quantized labels in less memory.
+#[test]
+fn compressed_clusters() {
+    let points = mixed_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 16.0,
+        eps_max: BASE_R / 8.0,
+        pts_min: 3.0,
+        pts_max: 5.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap().into_clusters();
+    let compressed = compress_clusters(clusters.clone());
+    assert_eq!(compressed.len(), clusters.len());
+    let compressed_bytes: usize = compressed
+        .iter()
+        .map(CompressedCluster::size_in_bytes)
+        .sum();
+    let assignments: usize = clusters.iter().map(Vec::len).sum();
+    assert!(compressed_bytes * 4 < assignments * std::mem::size_of::<Assignment>());
+    let decompressed: Vec<Cluster> = decompress_clusters(compressed);
+    for (mut cluster, decompressed) in clusters.into_iter().zip(decompressed) {
+        cluster.sort_by_key(|a| a.index);
+        assert_eq!(cluster.len(), decompressed.len());
+        for (a, b) in cluster.iter().zip(&decompressed) {
+            assert_eq!((a.index, a.cluster_id), (b.index, b.cluster_id));
+            assert_eq!(a.category, b.category);
+            assert!((a.label - b.label).abs() <= 0.5 / 255.0);
+        }
+    }
+}
+
+// Centroids should be the label-weighted mean of all non-noise points.
+#[test]
+fn centroids() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let mut centroids = cluster_centroids(&points, &clusters);
+    assert_eq!(centroids.pop(), Some(None));
+    centroids.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(centroids, vec![Some([0.75]), Some([4.75])]);
+}
+
+// The elbow of the k-distance graph should suggest an eps on the order of the cluster radius.
+#[test]
+fn k_distance_elbow() {
+    let outliers: Vec<Point> = (1..=10)
+        .flat_map(|i| gaussian_circle(1, BASE_R * 10.0 * i as f64, BASE_R * 20.0, 0.0))
+        .collect();
+    let points = flat_vec![bimodal_gaussian(), outliers,];
+    let distances = heuristics::k_distance_graph(&points, 4).unwrap();
+    assert_eq!(distances.len(), points.len());
+    assert!(distances.windows(2).all(|w| w[0] <= w[1]));
+    let eps = distances[heuristics::elbow_index(&distances)];
+    assert!(eps > BASE_R / 10.0 && eps < BASE_R, "{}", eps);
+    assert_eq!(
+        heuristics::k_distance_graph(&points, 0),
+        Err(FuzzyDBSCANError::InvalidNeighborCount)
+    );
+    // NaN distances should be sorted last.
+    let distances = heuristics::k_distance_graph(&line(&[0.0, f64::NAN, 1.0, 3.0]), 1).unwrap();
+    assert_eq!(distances[..3], [1.0, 1.0, 2.0]);
+    assert!(distances[3].is_nan());
+}
+
+// Parameters should be saved as human-readable JSON.
+#[cfg(feature = "json")]
+#[test]
+fn json_parameters() {
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.5,
+        eps_max: 2.5,
+        pts_min: 3.0,
+        pts_max: 4.0,
+        min_cluster_size: 2,
+        border_label_mode: BorderLabelMode::MaxCore,
+        ..Default::default()
+    };
+    let json = fuzzy_dbscan.to_json();
+    assert!(json.contains(r#""eps_min": 1.5"#));
+    assert!(json.contains(r#""border_label_mode": "MaxCore""#));
+    let parsed = FuzzyDBSCAN::<f64>::from_json(&json).unwrap();
+    assert_eq!(parsed.to_json(), json);
+    assert_eq!((parsed.eps_min, parsed.eps_max), (1.5, 2.5));
+    assert_eq!(parsed.border_label_mode, BorderLabelMode::MaxCore);
+    let json = r#"{"eps_min": 1, "eps_max": 2, "pts_min": 3, "pts_max": 4, "min_cluster_size": 1}"#;
+    let parsed = FuzzyDBSCAN::<f64>::from_json(json).unwrap();
+    assert_eq!(parsed.progress_interval, 100);
+    assert!(FuzzyDBSCAN::<f64>::from_json("{}").is_err());
+}
+
+// Clusters and parameters should survive a JSON round-trip.
+#[test]
+fn serde_round_trip() {
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R,
+        pts_min: 3.0,
+        pts_max: 10.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let json = serde_json::to_string(&clusters).unwrap();
+    assert!(json.contains(r#""category":"core""#));
+    let parsed: Vec<Cluster> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, *clusters);
+    let json = serde_json::to_string(&fuzzy_dbscan).unwrap();
+    let parsed: FuzzyDBSCAN = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.eps_min, fuzzy_dbscan.eps_min);
+    assert_eq!(parsed.eps_max, fuzzy_dbscan.eps_max);
+    assert_eq!(parsed.pts_min, fuzzy_dbscan.pts_min);
+    assert_eq!(parsed.pts_max, fuzzy_dbscan.pts_max);
+    assert_eq!(parsed.min_cluster_size, fuzzy_dbscan.min_cluster_size);
+}
+
+// Clusters should survive a bincode round-trip in much less space than JSON.
+#[cfg(feature = "bincode")]
+#[test]
+fn bincode_round_trip() {
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R,
+        pts_min: 3.0,
+        pts_max: 10.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let data = clusters_to_bincode(&clusters);
+    let json = serde_json::to_string(&clusters).unwrap();
+    assert!((data.len() as f64) < json.len() as f64 * 0.3);
+    let parsed: Vec<Cluster> = clusters_from_bincode(&data).unwrap();
+    assert_eq!(parsed, *clusters);
+    assert!(clusters_from_bincode::<f64>(&data[..data.len() - 1]).is_err());
+}
+
+// The Python bindings should return clusters as lists of dictionaries.
+#[cfg(feature = "python")]
+#[test]
+fn python_bindings() {
+    use fuzzy_dbscan::pyo3::prelude::*;
+    use fuzzy_dbscan::pyo3::py_run;
+    fuzzy_dbscan::pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let fuzzy_dbscan = py.get_type::<PyFuzzyDBSCAN>();
+        py_run!(
+            py,
+            fuzzy_dbscan,
+            r#"
+points = [(0.0, 0.0), (1.0, 0.0), (2.75, 0.0), (4.5, 0.0), (5.5, 0.0), (10.0, 0.0)]
+clusters = fuzzy_dbscan(1.0, 2.0, 2.0, 2.0).cluster(points)
+assert len(clusters) == 3, clusters
+assert clusters[0][0] == {"index": 0, "cluster_id": 0, "label": 1.0, "category": "core"}
+assert clusters[2] == [{"index": 5, "cluster_id": None, "label": 1.0, "category": "noise"}]
+try:
+    fuzzy_dbscan(2.0, 1.0, 2.0, 2.0).cluster(points)
+    assert False
+except ValueError as error:
+    assert "eps_min" in str(error)
+"#
+        );
+    });
+}
+
+// The C bindings should reject more coordinates than fit into a slice without reading them.
+#[cfg(feature = "ffi")]
+#[test]
+fn c_bindings_overflow() {
+    use fuzzy_dbscan::ffi;
+    let fuzzy_dbscan = ffi::fuzzy_dbscan_create(1.0, 2.0, 2.0, 2.0);
+    let points = [0.0f32; 2];
+    let mut n_assignments = 1;
+    for (n_points, dimensions) in [(usize::MAX / 2 + 1, 2), (usize::MAX / 8, 2)] {
+        let assignments = unsafe {
+            ffi::fuzzy_dbscan_cluster(
+                fuzzy_dbscan,
+                points.as_ptr(),
+                n_points,
+                dimensions,
+                &mut n_assignments,
+            )
+        };
+        assert!(assignments.is_null());
+        assert_eq!(n_assignments, 0);
+    }
+    unsafe { ffi::fuzzy_dbscan_free(fuzzy_dbscan) };
+}
+
+// The C bindings should link into a C program and return the same assignments.
+#[cfg(all(feature = "ffi", target_os = "linux"))]
+#[test]
+fn c_bindings() {
+    use std::path::Path;
+    use std::process::Command;
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+    // Integration tests only build the library as an rlib, thus build the shared library.
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--lib", "--features", "ffi", "--target-dir"])
+        .arg(&target_dir)
+        .current_dir(manifest_dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let library_dir = target_dir.join("debug");
+    let source = target_dir.join("main.c");
+    let program = target_dir.join("main");
+    std::fs::write(
+        &source,
+        r#"
+#include <stdio.h>
+#include "fuzzy_dbscan.h"
+
+int main(void) {
+    const float points[] = {0.0f, 1.0f, 2.75f, 4.5f, 5.5f, 10.0f};
+    size_t n_assignments;
+    if (fuzzy_dbscan_create(2.0f, 1.0f, 2.0f, 2.0f) != NULL) {
+        return 1;
+    }
+    FuzzyDBSCAN *fuzzy_dbscan = fuzzy_dbscan_create(1.0f, 2.0f, 2.0f, 2.0f);
+    FfiAssignment *assignments = fuzzy_dbscan_cluster(fuzzy_dbscan, points, 6, 1, &n_assignments);
+    for (size_t i = 0; i < n_assignments; i++) {
+        FfiAssignment a = assignments[i];
+        printf("%zu %lld %.2f %u\n", a.index, (long long)a.cluster_id, a.label, a.category);
+    }
+    fuzzy_dbscan_free_result(assignments, n_assignments);
+    fuzzy_dbscan_free(fuzzy_dbscan);
+    return 0;
+}
+"#,
+    )
+    .unwrap();
+    let status = Command::new("cc")
+        .arg(&source)
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .args(["-lfuzzy_dbscan", "-o"])
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    // Cargo may add an outdated build of the library to the search path.
+    let output = Command::new(&program)
+        .env_remove("LD_LIBRARY_PATH")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let points: Vec<NDPoint<f32>> = [0.0, 1.0, 2.75, 4.5, 5.5, 10.0]
+        .iter()
+        .map(|&x| NDPoint::new(vec![x]))
+        .collect();
+    let fuzzy_dbscan = FuzzyDBSCAN::<f32> {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let expected: String = fuzzy_dbscan
+        .cluster(&points)
+        .unwrap()
+        .iter()
+        .flatten()
+        .map(|a| {
+            let category = match a.category {
+                Category::Core => 0,
+                Category::Border => 1,
+                Category::Noise => 2,
+                _ => unreachable!(),
+            };
+            let cluster_id = a.cluster_id.map_or(-1, |cluster_id| cluster_id as i64);
+            format!("{} {} {:.2} {}\n", a.index, cluster_id, a.label, category)
+        })
+        .collect();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+}
+
+// Clusters should survive a CSV round-trip.
+#[cfg(feature = "csv")]
+#[test]
+fn csv_round_trip() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let mut csv = Vec::new();
+    write_clusters_csv(&mut csv, &clusters).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.starts_with("cluster_id,point_index,label,category\n"));
+    assert!(csv.ends_with("-1,5,1.0,noise\n"));
+    let parsed: Vec<Cluster> = read_clusters_csv(csv.as_bytes()).unwrap();
+    assert_eq!(parsed, *clusters);
+    assert!(read_clusters_csv::<_, f64>("cluster_id\nfoo\n".as_bytes()).is_err());
+}
+
+// Clustering should reduce to classic DBSCAN for all membership functions.
+#[test]
+fn membership_functions() {
+    let points = mixed_gaussian();
+    let cluster = |core: Box<dyn MembershipFn>, border: Box<dyn MembershipFn>| {
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min: BASE_R / 2.0,
+            eps_max: BASE_R / 2.0,
+            pts_min: 10.0,
+            pts_max: 10.0,
+            core_membership: core,
+            border_membership: border,
+            ..Default::default()
+        };
+        normalized(fuzzy_dbscan.cluster(&points).unwrap())
+    };
+    let linear = cluster(Box::new(LinearMembership), Box::new(LinearMembership));
+    let gaussian = cluster(
+        Box::new(GaussianMembership { sigma_factor: 0.5 }),
+        Box::new(GaussianMembership { sigma_factor: 0.5 }),
+    );
+    let sigmoid = cluster(
+        Box::new(SigmoidMembership { steepness: 10.0 }),
+        Box::new(SigmoidMembership { steepness: 10.0 }),
+    );
+    assert_eq!(gaussian, linear);
+    assert_eq!(sigmoid, linear);
+    assert!(linear
+        .iter()
+        .flatten()
+        .all(|&(_, _, label)| label == 0.0 || label == 1e9));
+    let steps = [0.0, 0.25, 0.5, 0.75, 1.0];
+    for membership in [
+        &LinearMembership as &dyn MembershipFn,
+        &GaussianMembership { sigma_factor: 0.5 },
+        &SigmoidMembership { steepness: 10.0 },
+    ] {
+        let labels: Vec<f64> = steps
+            .iter()
+            .map(|&x| membership.eval(x, 0.0, 1.0))
+            .collect();
+        assert_eq!(labels[4], 1.0);
+        assert!(labels.windows(2).all(|w| w[0] <= w[1]), "{:?}", labels);
+        assert_eq!(membership.eval(-0.1, 0.0, 1.0), 0.0);
+    }
+}
+
+// The reachability ordering should visit clusters consecutively, separated by infinite valleys.
+#[test]
+fn reachability_ordering() {
+    let points = line(&[0.0, 1.0, 2.5, 10.0, 11.0, 20.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 3.0,
+        ..Default::default()
+    };
+    let order = fuzzy_dbscan.reachability_order(&points).unwrap();
+    let summary: Vec<(usize, f64, f64)> = order
+        .iter()
+        .map(|e| (e.point_index, e.core_distance, e.reachability_distance))
+        .collect();
+    let inf = f64::INFINITY;
+    assert_eq!(
+        summary,
+        vec![
+            (0, 1.0, inf),
+            (1, 1.0, 1.0),
+            (2, 1.5, 1.5),
+            (3, 1.0, inf),
+            (4, 1.0, 1.0),
+            (5, inf, inf),
+        ]
+    );
+    assert_eq!(
+        FuzzyDBSCAN::<f64>::builder()
+            .eps_min(1.0)
+            .eps_max(2.0)
+            .pts_min(1.0)
+            .pts_max(2.0)
+            .build()
+            .unwrap()
+            .reachability_order::<Point1D>(&[]),
+        Err(FuzzyDBSCANError::EmptyInput)
+    );
+}
+
+// Reachability distances of neighboring core points should equal their distance.
+#[test]
+fn reachability_distance() {
+    let points = line(&[0.0, 1.0, 2.5, 10.0, 11.0, 20.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 3.0,
+        ..Default::default()
+    };
+    assert_eq!(fuzzy_dbscan.reachability_distance(&points, 0, 1, 1), 1.0);
+    assert_eq!(fuzzy_dbscan.reachability_distance(&points, 2, 1, 1), 1.5);
+    assert_eq!(fuzzy_dbscan.reachability_distance(&points, 3, 4, 1), 1.0);
+    // Core distances bound the reachability distance, even of a point to itself.
+    assert_eq!(fuzzy_dbscan.reachability_distance(&points, 1, 1, 2), 1.5);
+    assert_eq!(
+        fuzzy_dbscan.reachability_distance(&points, 0, 1, 2),
+        f64::INFINITY
+    );
+    assert_eq!(
+        fuzzy_dbscan.reachability_distance(&points, 0, 5, 1),
+        f64::INFINITY
+    );
+    assert_eq!(fuzzy_dbscan.reachability_distance(&points, 0, 2, 0), 2.5);
+}
+
+// Clustering should be reproducible if deterministic.
+#[cfg(feature = "deterministic")]
+#[test]
+fn deterministic_output() {
+    let points = mixed_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R,
+        pts_min: 5.0,
+        pts_max: 20.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(fuzzy_dbscan.cluster(&points).unwrap(), clusters);
+    assert_eq!(clusters[0][0].index, 0);
+}
+
+// Points should be found by category, even if they belong to multiple clusters.
+#[test]
+fn points_by_category() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(
+        clusters
+            .iter()
+            .flatten()
+            .filter(|a| a.index == 2 && a.category == Category::Border)
+            .count(),
+        2
+    );
+    assert_eq!(query::core_points(&clusters), vec![0, 1, 3, 4]);
+    assert_eq!(query::border_points(&clusters), vec![2]);
+    assert_eq!(query::noise_points(&clusters), vec![5]);
+}
+
+// Both clusters of a bimodal gaussian should have similar sizes.
+#[test]
+fn cluster_sizes() {
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R,
+        pts_min: 3.0,
+        pts_max: 10.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let sizes = query::cluster_sizes(&clusters);
+    assert_eq!(sizes.len(), clusters.len());
+    for (size, cluster) in sizes.iter().zip(&clusters) {
+        assert_eq!(size.0, cluster.len());
+        assert_eq!(size.0, size.1 + size.2 + size.3);
+    }
+    let non_noise: Vec<usize> = sizes
+        .iter()
+        .filter(|size| size.3 == 0)
+        .map(|size| size.0)
+        .collect();
+    assert_eq!(non_noise.len(), 2);
+    let (smaller, larger) = (
+        non_noise[0].min(non_noise[1]),
+        non_noise[0].max(non_noise[1]),
+    );
+    assert!(smaller as f64 >= 0.8 * larger as f64);
+    let largest = query::largest_cluster(&clusters).unwrap();
+    assert_eq!(clusters[largest].len(), larger);
+    assert_eq!(query::largest_cluster::<f64>(&[]), None);
+}
+
+// Fixed clusters should be kept while new points form new clusters.
+#[test]
+fn partial_recluster() {
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let fixed: Vec<(usize, Assignment)> = clusters
+        .iter()
+        .enumerate()
+        .flat_map(|(cluster_index, cluster)| {
+            cluster.iter().map(move |a| (cluster_index, a.clone()))
+        })
+        .filter(|(_, a)| a.category != Category::Noise)
+        .collect();
+    let reclustered = fuzzy_dbscan.partial_recluster(&points, &fixed).unwrap();
+    assert_eq!(
+        query::cluster_indices(&reclustered),
+        query::cluster_indices(&clusters)
+    );
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0, 10.5, 11.0, 20.0]);
+    let reclustered = fuzzy_dbscan.partial_recluster(&points, &fixed).unwrap();
+    assert_eq!(
+        query::cluster_indices(&reclustered),
+        vec![vec![0, 1, 2], vec![2, 3, 4], vec![5, 6, 7], vec![8]]
+    );
+    assert!(reclustered[1].iter().all(|a| a.cluster_id == Some(1)));
+    // Unused cluster indices should not leave empty clusters.
+    let gaps: Vec<(usize, Assignment)> = fixed
+        .iter()
+        .map(|(cluster_index, a)| (2 * cluster_index + 1, a.clone()))
+        .collect();
+    let reclustered = fuzzy_dbscan.partial_recluster(&points, &gaps).unwrap();
+    assert_eq!(
+        query::cluster_indices(&reclustered),
+        vec![vec![0, 1, 2], vec![2, 3, 4], vec![5, 6, 7], vec![8]]
+    );
+    assert!(reclustered[1].iter().all(|a| a.cluster_id == Some(1)));
+    assert_eq!(
+        fuzzy_dbscan::validate_clusters(points.len(), &reclustered),
+        Ok(())
+    );
+    // A new core connecting two fixed clusters should merge them.
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 1.0,
+        ..fuzzy_dbscan
+    };
+    let points = line(&[0.0, 0.5, 2.3, 2.8]);
+    let fixed: Vec<(usize, Assignment)> = fuzzy_dbscan
+        .cluster(&points)
+        .unwrap()
+        .iter()
+        .enumerate()
+        .flat_map(|(cluster_index, cluster)| {
+            cluster.iter().map(move |a| (cluster_index, a.clone()))
+        })
+        .collect();
+    assert_eq!(fixed.iter().map(|(c, _)| c).max(), Some(&1));
+    let points = line(&[0.0, 0.5, 2.3, 2.8, 1.4]);
+    let reclustered = fuzzy_dbscan.partial_recluster(&points, &fixed).unwrap();
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(normalized(reclustered), normalized(clusters.to_vec()));
+}
+
+// A dry run should only compute core labels, which are crisp for crisp densities.
+#[test]
+fn dry_run() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let mut fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let labels = fuzzy_dbscan.dry_run(&points).unwrap();
+    assert_eq!(labels, vec![1.0, 1.0, 0.0, 1.0, 1.0, 0.0]);
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(query::core_points(&clusters), vec![0, 1, 3, 4]);
+    fuzzy_dbscan.pts_max = 3.0;
+    let labels = fuzzy_dbscan.dry_run(&points).unwrap();
+    assert!(labels.iter().all(|&label| (0.0..=1.0).contains(&label)));
+    assert!(labels[1] > 0.0 && labels[1] < 1.0);
+    fuzzy_dbscan.pts_min = 4.0;
+    assert!(fuzzy_dbscan.dry_run(&points).is_err());
+}
+
+// Cluster results should separate the noise cluster from the real clusters.
+#[test]
+fn cluster_results() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let result = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(result.len(), 3);
+    assert_eq!(result.cluster_count(), 2);
+    assert_eq!(result.real_clusters(), &result[..2]);
+    assert_eq!(result.noise().map(|noise| noise[0].index), Some(5));
+    assert_eq!(result.point_cluster(0), Some((0, 1.0)));
+    assert_eq!(result.point_cluster(2), Some((0, 0.25)));
+    assert_eq!(result.point_cluster(3), Some((1, 1.0)));
+    assert!(result.is_noise(5));
+    assert!(!result.is_noise(2));
+    let result = fuzzy_dbscan.cluster(&points[..5]).unwrap();
+    assert_eq!(result.noise(), None);
+    assert_eq!(result.real_clusters().len(), result.cluster_count());
+    assert_eq!(result.into_clusters().len(), 2);
+}
+
+// Cluster indices should list shared border points in each cluster and noise last.
+#[test]
+fn indices_by_cluster() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let mut clusters = fuzzy_dbscan.cluster(&points).unwrap().into_clusters();
+    let expected = vec![vec![0, 1, 2], vec![2, 3, 4], vec![5]];
+    assert_eq!(query::cluster_indices(&clusters), expected);
+    clusters.rotate_right(1);
+    assert_eq!(query::cluster_indices(&clusters), expected);
+}
+
+// Core connectivity graphs should connect the cores of each cluster, weighted by their membership.
+#[test]
+fn core_connectivity() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let mut fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let edges = fuzzy_dbscan.core_connectivity_graph(&points).unwrap();
+    assert_eq!(edges, vec![(0, 1, 1.0), (3, 4, 1.0)]);
+    fuzzy_dbscan.pts_min = 1.0;
+    fuzzy_dbscan.pts_max = 1.0;
+    let edges = fuzzy_dbscan
+        .core_connectivity_graph(&line(&[0.0, 1.5, 3.0]))
+        .unwrap();
+    assert_eq!(edges, vec![(0, 1, 0.5), (0, 2, 0.5), (1, 2, 0.5)]);
+    // Cores exactly eps_max apart should be connected in one component.
+    let points = line(&[0.0, 2.0, 4.0]);
+    assert_eq!(fuzzy_dbscan.cluster(&points).unwrap().cluster_count(), 1);
+    let edges = fuzzy_dbscan.core_connectivity_graph(&points).unwrap();
+    assert_eq!(edges, vec![(0, 1, 0.0), (0, 2, 0.0), (1, 2, 0.0)]);
+}
+
+// Border points exactly eps_max away from their cores should have a zero label.
+#[test]
+fn border_labels_without_contributions() {
+    let points = line(&[0.0, 1.0, 3.0]);
+    for border_label_mode in [BorderLabelMode::MinCore, BorderLabelMode::MaxCore] {
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min: 1.0,
+            eps_max: 2.0,
+            pts_min: 2.0,
+            pts_max: 2.0,
+            border_label_mode,
+            ..Default::default()
+        };
+        let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+        assert_eq!(clusters.len(), 1);
+        assert!(clusters[0].iter().all(|a| a.label != f64::MAX));
+        let border = clusters[0].iter().find(|a| a.index == 2).unwrap();
+        assert_eq!(border.category, Category::Border);
+        assert_eq!(border.label, 0.0);
+        let reclustered = fuzzy_dbscan.partial_recluster(&points, &[]).unwrap();
+        assert_eq!(normalized(reclustered), normalized(clusters.to_vec()));
+    }
+}
+
+// Neighbor graphs should list all neighbors within eps_max, regardless of their category.
+#[test]
+fn neighbor_graph() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    assert_eq!(
+        fuzzy_dbscan.neighbor_graph(&points),
+        vec![
+            vec![(1, 1.0)],
+            vec![(0, 1.0), (2, 0.25)],
+            vec![(1, 0.25), (3, 0.25)],
+            vec![(2, 0.25), (4, 1.0)],
+            vec![(3, 1.0)],
+            vec![],
+        ]
+    );
+}
+
+// Merging two clusters should keep each point once with its highest label and category.
+#[test]
+fn merge_two_clusters() {
+    let assignment = |index: usize, cluster_id: usize, label: f64, category: Category| Assignment {
+        index,
+        cluster_id: Some(cluster_id),
+        label,
+        category,
+    };
+    let a = vec![
+        assignment(0, 0, 1.0, Category::Core),
+        assignment(1, 0, 0.25, Category::Border),
+        assignment(2, 0, 0.5, Category::Border),
+    ];
+    let b = vec![
+        assignment(1, 1, 0.75, Category::Core),
+        assignment(2, 1, 0.25, Category::Border),
+        assignment(3, 1, 1.0, Category::Core),
+    ];
+    let merged = merge_clusters(a.clone(), b.clone());
+    assert_eq!(
+        merged,
+        vec![
+            assignment(0, 0, 1.0, Category::Core),
+            assignment(1, 0, 0.75, Category::Core),
+            assignment(2, 0, 0.5, Category::Border),
+            assignment(3, 0, 1.0, Category::Core),
+        ]
+    );
+    let noise = vec![Assignment {
+        index: 2,
+        cluster_id: None,
+        label: 1.0,
+        category: Category::Noise,
+    }];
+    let merged = merge_clusters(noise, a);
+    assert_eq!(merged[0].category, Category::Border);
+    assert!(merged.iter().all(|a| a.cluster_id.is_none()));
+    assert_eq!(merge_clusters(Vec::new(), b.clone()), b);
+}
+
+// Clusters should merge if they share enough border points, keeping each point once.
+#[test]
+fn overlapping_clusters() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let kept = merge_overlapping_clusters(clusters.clone().into_clusters(), 1.0);
+    assert_eq!(
+        query::cluster_indices(&kept),
+        query::cluster_indices(&clusters)
+    );
+    let merged = merge_overlapping_clusters(clusters.into_clusters(), 0.5);
+    assert_eq!(merged.len(), 2);
+    assert_eq!(
+        query::cluster_indices(&merged),
+        vec![vec![0, 1, 2, 3, 4], vec![5]]
+    );
+    let border: Vec<_> = merged[0].iter().filter(|a| a.index == 2).collect();
+    assert_eq!(border.len(), 1);
+    assert_eq!(border[0].category, Category::Border);
+    assert_eq!(border[0].label, 0.25);
+    assert!(merged[0].iter().all(|a| a.cluster_id == Some(0)));
+}
+
+// Both clusters of a bimodal gaussian should share border points in the valley between them.
+#[test]
+fn cluster_overlaps() {
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 10.0,
+        eps_max: BASE_R * 3.6,
+        pts_min: BASE_N as f64 * 0.2,
+        pts_max: BASE_N as f64 * 0.225,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let overlaps = cluster_overlap_matrix(&clusters);
+    assert_eq!(overlaps.len(), clusters.len());
+    assert_eq!((overlaps[0][0], overlaps[1][1]), (1.0, 1.0));
+    assert!(overlaps[0][1] > 0.0);
+    assert_eq!(overlaps[0][1], overlaps[1][0]);
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let clusters = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    }
+    .cluster(&points)
+    .unwrap();
+    let overlaps = cluster_overlap_matrix(&clusters);
+    assert_eq!(overlaps[0], vec![1.0, 1.0, 0.0]);
+    assert_eq!(overlaps[2], vec![0.0, 0.0, 1.0]);
+}
+
+// Cluster identifiers should match the positions of clusters and be missing for noise.
+#[test]
+fn cluster_ids() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0, 20.0, 21.0, 22.0, 23.0]);
+    let mut fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let check = |clusters: &[Cluster]| {
+        for (cluster_id, cluster) in clusters.iter().enumerate() {
+            for a in cluster {
+                match a.category {
+                    Category::Noise => assert_eq!(a.cluster_id, None),
+                    _ => assert_eq!(a.cluster_id, Some(cluster_id)),
+                }
+            }
+        }
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(clusters.len(), 4);
+    check(&clusters);
+    // Removing the first clusters must renumber the remaining one.
+    fuzzy_dbscan.min_cluster_size = 4;
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(query::cluster_indices(&clusters)[0], vec![6, 7, 8, 9]);
+    check(&clusters);
+}
+
+// Sorting assignments should rank them by descending label.
+#[test]
+fn ranked_assignments() {
+    let points = mixed_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 4.0,
+        eps_max: BASE_R / 2.0,
+        pts_min: 5.0,
+        pts_max: 20.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let mut cluster = clusters[0].clone();
+    let highest = cluster.iter().map(|a| a.label).fold(0.0, f64::max);
+    let lowest = cluster.iter().map(|a| a.label).fold(1.0, f64::min);
+    assert!(lowest < highest);
+    cluster.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(cluster[0].label, highest);
+    assert_eq!(cluster.last().unwrap().label, lowest);
+    assert!(cluster.windows(2).all(|w| w[0] <= w[1]));
+    let top = query::top_n(&clusters[0], 3);
+    assert_eq!(top.len(), 3);
+    assert_eq!(top[0], &cluster[0]);
+    assert_eq!(query::top_n(&clusters[0], usize::MAX).len(), cluster.len());
+}
+
+// New points should be classified like the points of the clustering they are compared with.
+#[test]
+fn classified_points() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let queries = line(&[0.5, 7.0, 15.0]);
+    assert_eq!(fuzzy_dbscan.density_at(&queries[0], &points), 3.0);
+    assert_eq!(fuzzy_dbscan.density_at(&queries[1], &points), 1.5);
+    let classify = |query| fuzzy_dbscan.classify_point(query, &clusters, &points);
+    let core = classify(&queries[0]);
+    assert_eq!(core.index, points.len());
+    assert_eq!(
+        (core.cluster_id, core.label, core.category),
+        (Some(0), 1.0, Category::Core)
+    );
+    let border = classify(&queries[1]);
+    assert_eq!(
+        (border.cluster_id, border.label, border.category),
+        (Some(1), 0.5, Category::Border)
+    );
+    let noise = classify(&queries[2]);
+    assert_eq!(
+        (noise.cluster_id, noise.label, noise.category),
+        (None, 1.0, Category::Noise)
+    );
+}
+
+// Clusters connected by a sparse bridge should merge once the bridge becomes dense enough.
+#[test]
+fn cluster_hierarchy() {
+    let points = line(&[
+        0.0, 0.2, 0.4, 0.6, 0.8, 1.7, 2.6, 3.5, 3.7, 3.9, 4.1, 4.3, 20.0, 20.2, 20.4, 20.6, 20.8,
+    ]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 1.0,
+        pts_min: 1.0,
+        pts_max: 1.0,
+        ..Default::default()
+    };
+    let hierarchy = fuzzy_dbscan
+        .hierarchical_merge(&points, &[3.0, 5.0])
+        .unwrap();
+    assert_eq!(hierarchy.pts_min_steps, vec![5.0, 3.0]);
+    assert_eq!(hierarchy.levels[0].len(), 3);
+    assert_eq!(hierarchy.levels[1].len(), 2);
+    let edges: Vec<(usize, usize, usize)> = hierarchy
+        .edges
+        .iter()
+        .map(|edge| (edge.level, edge.child, edge.parent))
+        .collect();
+    assert_eq!(edges, vec![(0, 0, 0), (0, 1, 0), (0, 2, 1)]);
+}
+
+// Outlier scores should be zero for core points and grow with the distance to core points.
+#[test]
+fn outlier_scores() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(
+        fuzzy_dbscan.outlier_scores(&points, &clusters),
+        vec![0.0, 0.0, 0.75, 0.0, 0.0, 1.0]
+    );
+}
+
+// Isolated points should have a higher local outlier factor than points inside clusters.
+#[test]
+fn local_outlier_factor() {
+    let points = flat_vec![
+        gaussian_circle(BASE_N / 4, 0.0, 0.0, BASE_R),
+        gaussian_circle(1, BASE_R * 2.0, 0.0, 0.0),
+    ];
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 4.0,
+        eps_max: BASE_R / 2.0,
+        pts_min: 3.0,
+        pts_max: 10.0,
+        ..Default::default()
+    };
+    let scores = fuzzy_dbscan.local_outlier_factor(&points, 5).unwrap();
+    assert_eq!(scores.len(), points.len());
+    let (interior, isolated) = scores.split_at(BASE_N / 4);
+    let mean = interior.iter().sum::<f64>() / interior.len() as f64;
+    assert!(mean < 1.5, "{}", mean);
+    assert!(isolated[0] > 1.5, "{}", isolated[0]);
+    assert!(interior.iter().all(|&score| score < isolated[0]));
+    // Without their own density, isolated points have no density at all.
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        self_included_in_density: false,
+        ..fuzzy_dbscan
+    };
+    let scores = fuzzy_dbscan.local_outlier_factor(&points, 5).unwrap();
+    assert_eq!(scores[BASE_N / 4], f64::INFINITY);
+    for k in [0, points.len()] {
+        assert_eq!(
+            fuzzy_dbscan.local_outlier_factor(&points, k),
+            Err(FuzzyDBSCANError::InvalidNeighborCount)
+        );
+    }
+}
+
+// Parameter sweeps should skip invalid combinations and lose noise with growing eps_max.
+#[test]
+fn parameter_sweep() {
+    let points = bimodal_gaussian();
+    let eps_max_values = [BASE_R / 8.0, BASE_R / 4.0, BASE_R / 2.0, BASE_R];
+    let results = heuristics::parameter_sweep(
+        &points,
+        &[BASE_R / 4.0],
+        &eps_max_values,
+        &[5.0, 10.0],
+        &[5.0],
+    );
+    assert_eq!(results.len(), 3);
+    assert!(results
+        .iter()
+        .all(|r| r.eps_max >= r.eps_min && r.pts_max >= r.pts_min));
+    assert!(results
+        .windows(2)
+        .all(|w| w[1].noise_fraction <= w[0].noise_fraction));
+    assert_eq!(results.last().unwrap().cluster_count, 2);
+    assert!(results.last().unwrap().silhouette > 0.5);
+}
+
+// Reclustering precomputed neighbors should match clustering with the same densities.
+#[test]
+fn precomputed_neighbors() {
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = |pts_min: f64, pts_max: f64| FuzzyDBSCAN {
+        eps_min: BASE_R / 4.0,
+        eps_max: BASE_R / 2.0,
+        pts_min,
+        pts_max,
+        ..Default::default()
+    };
+    let neighbors = fuzzy_dbscan(1.0, 1.0)
+        .precompute_neighbors(&points)
+        .unwrap();
+    for (pts_min, pts_max) in [(1.0, 1.0), (5.0, 10.0), (20.0, 40.0)] {
+        let expected = fuzzy_dbscan(pts_min, pts_max).cluster(&points).unwrap();
+        let clusters = fuzzy_dbscan(1.0, 1.0)
+            .recluster(&neighbors, pts_min, pts_max)
+            .unwrap();
+        assert_eq!(normalized(clusters), normalized(expected));
+    }
+    assert_eq!(
+        fuzzy_dbscan(1.0, 1.0).recluster(&neighbors, 2.0, 1.0),
+        Err(FuzzyDBSCANError::InvalidPtsRange)
+    );
+}
+
+// Progress should be reported at every interval and once all points have been visited.
+#[test]
+fn progress_reports() {
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R / 2.0,
+        pts_min: 5.0,
+        pts_max: 5.0,
+        progress_interval: 30,
+        ..Default::default()
+    };
+    let reports = std::cell::RefCell::new(Vec::new());
+    let clusters = fuzzy_dbscan
+        .cluster_with_progress(&points, |report| reports.borrow_mut().push(report))
+        .unwrap();
+    assert_eq!(
+        normalized(clusters.clone()),
+        normalized(fuzzy_dbscan.cluster(&points).unwrap())
+    );
+    let reports = reports.into_inner();
+    let n = points.len();
+    assert_eq!(reports.len(), n / 30 + 1);
+    assert!(reports.iter().all(|r| r.total_points == n));
+    assert!(reports
+        .windows(2)
+        .all(|w| w[0].points_visited < w[1].points_visited
+            && w[0].clusters_found <= w[1].clusters_found));
+    let last = reports.last().unwrap();
+    assert_eq!(last.points_visited, n);
+    let noise = clusters.iter().filter(|c| c[0].category == Category::Noise);
+    assert_eq!(last.clusters_found, clusters.len() - noise.count());
+}
+
+// The adjusted Rand index should be one for identical and close to zero for random clusterings.
+#[test]
+fn adjusted_rand() {
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R / 2.0,
+        pts_min: 5.0,
+        pts_max: 5.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let n = points.len();
+    assert_eq!(metrics::adjusted_rand_index(&clusters, &clusters, n), 1.0);
+    let permuted: Vec<Cluster> = clusters
+        .iter()
+        .map(|cluster| {
+            cluster
+                .iter()
+                .map(|a| Assignment {
+                    index: a.index * 7 % n,
+                    ..a.clone()
+                })
+                .collect()
+        })
+        .collect();
+    let ari = metrics::adjusted_rand_index(&clusters, &permuted, n);
+    assert!(ari.abs() < 0.1, "{}", ari);
+}
+
+// Clusters of a bimodal gaussian should match the two generating distributions.
+#[test]
+fn normalized_mutual_information() {
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R,
+        pts_min: 3.0,
+        pts_max: 10.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let predicted = defuzzify(points.len(), &clusters);
+    let ground_truth: Vec<usize> = (0..points.len()).map(|i| i / (BASE_N / 4)).collect();
+    let nmi: f64 = metrics::normalized_mutual_information(&predicted, &ground_truth);
+    assert!(nmi > 0.8, "{}", nmi);
+    let nmi: f64 = metrics::normalized_mutual_information(
+        &ground_truth
+            .iter()
+            .map(|&label| Some(label))
+            .collect::<Vec<_>>(),
+        &ground_truth,
+    );
+    assert!((nmi - 1.0).abs() < 1e-9);
+}
+
+// Border points should keep their memberships in all clusters.
+#[test]
+fn multi_assignments() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let multi = fuzzy_dbscan.cluster_multi(&points).unwrap();
+    assert_eq!(multi.len(), points.len());
+    assert!(multi.iter().enumerate().all(|(i, m)| m.index == i));
+    for &core in &[0, 1, 3, 4] {
+        assert_eq!(multi[core].memberships.len(), 1);
+        assert_eq!(multi[core].memberships[0].1, 1.0);
+    }
+    let mut shared = multi[2].memberships.clone();
+    shared.sort_by_key(|&(cluster_id, _)| cluster_id);
+    assert_eq!(shared, vec![(0, 0.25), (1, 0.25)]);
+    assert!(multi[5].memberships.is_empty());
+}
+
+// Region queries should return sorted neighbors, excluding the query point.
+#[test]
+fn sorted_region_query() {
+    use index::SpatialIndex;
+    let points = line(&[3.0, 0.0, 2.0, 1.0, 5.0, 1.5]);
+    let index: index::BruteForce = SpatialIndex::build(&points, 1.0);
+    assert_eq!(index.region_query(&points, 2), vec![0, 3, 5]);
+    assert_eq!(index.region_query(&points, 4), Vec::<usize>::new());
+}
+
+// Brute-force region queries should return exactly the distances of custom metrics.
+#[test]
+fn brute_force_exact_distances() {
+    use index::SpatialIndex;
+    struct Scaled(f64);
+    impl MetricSpace for Scaled {
+        fn distance(&self, other: &Self) -> f64 {
+            (self.0 - other.0).abs() * 1.1
+        }
+    }
+    // Squares of tiny distances underflow, so their square roots are off.
+    let points: Vec<Scaled> = (0..50)
+        .map(|i| Scaled(f64::from(i) * if i % 2 == 0 { 0.37 } else { 1e-170 }))
+        .collect();
+    let index: index::BruteForce = SpatialIndex::build(&points, 5.0);
+    for point_index in 0..points.len() {
+        let neighbors = index.region_query_with_distances(&points, point_index);
+        assert!(!neighbors.is_empty());
+        for (neighbor_index, distance) in neighbors {
+            let expected = points[point_index].distance(&points[neighbor_index]);
+            assert_eq!(distance.to_bits(), expected.to_bits());
+            assert!(distance <= 5.0);
+        }
+    }
+}
+
+// Rows of the partition matrix should only exceed one for shared border points.
+#[test]
+fn partition_matrix() {
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R / 2.0,
+        pts_min: 5.0,
+        pts_max: 5.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let matrix = cluster_to_partition_matrix(points.len(), &clusters);
+    assert_eq!(matrix.len(), points.len());
+    assert!(matrix.iter().all(|row| row.len() == clusters.len()));
+    assert!(matrix.iter().all(|row| row.iter().sum::<f64>() <= 1.0));
+    let points = line(&[0.0, 0.1, 0.2, 1.0, 2.0, 3.0, 3.8, 3.9, 4.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 1.0,
+        pts_min: 4.0,
+        pts_max: 4.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let matrix = cluster_to_partition_matrix(points.len(), &clusters);
+    assert_eq!(matrix[4], vec![1.0, 1.0]);
+    assert_eq!(matrix[0], vec![1.0, 0.0]);
+    assert_eq!(defuzzify(points.len(), &clusters)[4], Some(0));
+}
+
+// The built-in points should measure Euclidean distances.
+#[test]
+fn built_in_points() {
+    assert_eq!(
+        Point2D::new(0.0, 0.0).distance(&Point2D::from((3.0, 4.0))),
+        5.0
+    );
+    assert_eq!(Point2D64::new(1.0, 1.0).distance(&(4.0, 5.0).into()), 5.0);
+    assert_eq!(
+        Point2D64::new(1.0, 1.0).distance_squared(&(4.0, 5.0).into()),
+        25.0
+    );
+    assert_eq!([0.0, 0.0, 0.0].distance_squared(&[1.0, 2.0, 2.0]), 9.0);
+    let points: Vec<Point2D> = [(0.0, 0.0), (1.0, 0.0), (10.0, 0.0)]
+        .iter()
+        .map(|&xy| xy.into())
+        .collect();
+    let fuzzy_dbscan = FuzzyDBSCAN::builder()
+        .eps_min(1.0)
+        .eps_max(1.0)
+        .pts_min(2.0)
+        .pts_max(2.0)
+        .build()
+        .unwrap();
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(
+        defuzzify(points.len(), &clusters),
+        vec![Some(0), Some(0), None]
+    );
+}
+
+// Points of nalgebra should cluster like the built-in points.
+#[cfg(feature = "nalgebra")]
+#[test]
+fn nalgebra_points() {
+    let points = bimodal_gaussian();
+    let coordinates: Vec<[f32; 2]> = points
+        .iter()
+        .map(|point| point.coordinates().map(|coordinate| coordinate as f32))
+        .collect();
+    let built_in: Vec<Point2D> = coordinates.iter().map(|&[x, y]| (x, y).into()).collect();
+    let nalgebra_points: Vec<nalgebra::Point2<f32>> =
+        coordinates.iter().map(|&xy| xy.into()).collect();
+    let fuzzy_dbscan = FuzzyDBSCAN::<f32> {
+        eps_min: BASE_R as f32 / 2.0,
+        eps_max: BASE_R as f32,
+        pts_min: 3.0,
+        pts_max: 10.0,
+        ..Default::default()
+    };
+    let expected = fuzzy_dbscan.cluster(&built_in).unwrap();
+    let actual = fuzzy_dbscan.cluster(&nalgebra_points).unwrap();
+    assert_eq!(actual.len(), expected.len());
+    assert_eq!(nalgebra_points[0].coordinates(), coordinates[0]);
+}
+
+// Views of ndarray should cluster like the built-in points.
+#[cfg(feature = "ndarray")]
+#[test]
+fn ndarray_points() {
+    let points = bimodal_gaussian();
+    let coordinates: Vec<[f32; 2]> = points
+        .iter()
+        .map(|point| point.coordinates().map(|coordinate| coordinate as f32))
+        .collect();
+    let built_in: Vec<Point2D> = coordinates.iter().map(|&[x, y]| (x, y).into()).collect();
+    let array = ndarray::Array2::from(coordinates);
+    let views: Vec<ndarray::ArrayView1<f32>> = array.rows().into_iter().collect();
+    let fuzzy_dbscan = FuzzyDBSCAN::<f32> {
+        eps_min: BASE_R as f32 / 2.0,
+        eps_max: BASE_R as f32,
+        pts_min: 3.0,
+        pts_max: 10.0,
+        ..Default::default()
+    };
+    let expected = fuzzy_dbscan.cluster(&built_in).unwrap();
+    let actual = fuzzy_dbscan.cluster(&views).unwrap();
+    assert_eq!(actual.len(), expected.len());
+}
+
+// Shared and borrowed points should cluster like the points themselves.
+#[test]
+fn shared_points() {
+    let points = bimodal_gaussian();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 4.0,
+        eps_max: BASE_R / 2.0,
+        pts_min: 5.0,
+        pts_max: 10.0,
+        ..Default::default()
+    };
+    let expected = normalized(fuzzy_dbscan.cluster(&points).unwrap());
+    let shared: Vec<std::sync::Arc<Point>> = points.iter().cloned().map(Into::into).collect();
+    assert_eq!(normalized(fuzzy_dbscan.cluster(&shared).unwrap()), expected);
+    let borrowed: Vec<&Point> = points.iter().collect();
+    assert_eq!(
+        normalized(fuzzy_dbscan.cluster(&borrowed).unwrap()),
+        expected
+    );
+}
+
+// Embedding 10-dimensional points in 2D should keep their clusters apart.
+#[cfg(feature = "spectral")]
+#[test]
+fn spectral_embeddings() {
+    let n = BASE_N / 4;
+    let points = flat_vec![
+        gaussian_circle(n, 0.0, 0.0, BASE_R),
+        gaussian_circle(n, BASE_R * 4.0, 0.0, BASE_R),
+    ];
+    let scale = 1.0 / 5f64.sqrt();
+    let nd_points: Vec<NDPoint> = points
+        .iter()
+        .map(|point| {
+            let [x, y] = point.coordinates();
+            let mut coords = vec![x * scale; 5];
+            coords.extend(vec![y * scale; 5]);
+            NDPoint::new(coords)
+        })
+        .collect();
+    let matrix = DistanceMatrix::from_points(&nd_points, NDPoint::distance);
+    let embedded: Vec<NDPoint> = spectral_embedding(&matrix, 2)
+        .into_iter()
+        .map(NDPoint::new)
+        .collect();
+    assert_eq!(embedded[0].coords.len(), 2);
+    let gap = embedded[0].distance(&embedded[n]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: gap / 4.0,
+        eps_max: gap / 2.0,
+        pts_min: 3.0,
+        pts_max: 10.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&embedded).unwrap();
+    assert_eq!(clusters.cluster_count(), 2);
+    let predicted = defuzzify(points.len(), &clusters);
+    assert!(predicted[..n].iter().all(|&label| label == predicted[0]));
+    assert!(predicted[n..].iter().all(|&label| label == predicted[n]));
+    assert_ne!(predicted[0], predicted[n]);
+}
+
+// Clusters separated in different subspaces should be found in each of them.
+#[test]
+fn subspace_points() {
+    let n = BASE_N / 4;
+    // The first two dimensions separate both halves, the last two even and odd points.
+    let halves = bimodal_gaussian();
+    let parity = flat_vec![
+        gaussian_circle(n, 0.0, 0.0, BASE_R),
+        gaussian_circle(n, BASE_R * 5.0, 0.0, BASE_R),
+    ];
+    let points: Vec<SubspacePoint> = (0..2 * n)
+        .map(|i| {
+            let [x, y] = halves[i].coordinates();
+            let [z, w] = parity[i % 2 * n + i / 2].coordinates();
+            SubspacePoint::new(vec![x, y, z, w], vec![0, 1, 2, 3])
+        })
+        .collect();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R,
+        pts_min: 3.0,
+        pts_max: 10.0,
+        ..Default::default()
+    };
+    let subspaces = fuzzy_dbscan
+        .subspace_cluster(&points, &[vec![0, 1], vec![2, 3]])
+        .unwrap();
+    assert_eq!(subspaces[1].dims, [2, 3]);
+    let same = |a: usize, b: usize, clusters: &ClusterResult| {
+        let predicted = defuzzify(points.len(), clusters);
+        predicted[a].is_some() && predicted[a] == predicted[b]
+    };
+    for subspace in &subspaces {
+        assert_eq!(subspace.clusters.cluster_count(), 2);
+        assert!(subspace.silhouette > 0.5, "{}", subspace.silhouette);
+    }
+    assert!(same(0, 2, &subspaces[0].clusters) && !same(0, n, &subspaces[0].clusters));
+    assert!(same(0, n, &subspaces[1].clusters) && !same(0, 1, &subspaces[1].clusters));
+    let a = SubspacePoint::new(vec![0.0, 0.0], vec![0]);
+    let b = SubspacePoint::new(vec![3.0, 4.0], vec![0]);
+    assert_eq!(a.distance(&b), 3.0);
+    let c = SubspacePoint::new(vec![3.0, 4.0], vec![1]);
+    assert_eq!(a.distance(&c), 5.0);
+    assert_eq!(c.distance(&a), 5.0);
+}
+
+// Subspace points with a different number of dimensions cannot be compared.
+#[test]
+#[should_panic]
+fn mismatched_subspace_points() {
+    let a = SubspacePoint::new(vec![0.0, 0.0], vec![0]);
+    a.distance(&SubspacePoint::new(vec![3.0], vec![0]));
+}
+
+// Clustering 10-dimensional points should find the same structure as in 2D.
+#[test]
+fn nd_points() {
+    let points = bimodal_gaussian();
+    let scale = 1.0 / 5f64.sqrt();
+    let nd_points: Vec<NDPoint> = points
+        .iter()
+        .map(|point| {
+            let [x, y] = point.coordinates();
+            let mut coords = vec![x * scale; 5];
+            coords.extend(vec![y * scale; 5]);
+            NDPoint::new(coords)
+        })
+        .collect();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: BASE_R / 2.0,
+        eps_max: BASE_R,
+        pts_min: 3.0,
+        pts_max: 10.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    let nd_clusters = fuzzy_dbscan.cluster(&nd_points).unwrap();
+    assert_eq!(nd_clusters.len(), clusters.len());
+    let a = NDPointWith::<distance::Manhattan>::new(vec![0.0, 0.0]);
+    let b = NDPointWith::<distance::Manhattan>::new(vec![3.0, -4.0]);
+    assert_eq!(a.distance(&b), 7.0);
+    assert_eq!(a.distance_squared(&b), 49.0);
+    let a = NDPointWith::<distance::Chebyshev>::new(vec![0.0, 0.0]);
+    let b = NDPointWith::<distance::Chebyshev>::new(vec![3.0, -4.0]);
+    assert_eq!(a.distance(&b), 4.0);
+    let a = NDPointWith::<distance::Cosine>::new(vec![1.0, 0.0]);
+    let b = NDPointWith::<distance::Cosine>::new(vec![0.0, 2.0]);
+    assert_eq!(a.distance(&b), 1.0);
+}
+
+// Points without coordinates are invalid.
+#[test]
+#[should_panic]
+fn empty_nd_point() {
+    NDPoint::<f64>::new(Vec::new());
+}
+
+// Geographic points should be clustered by their distance in kilometers.
+#[test]
+fn geo_points() {
+    use geo::GeoPoint;
+    let berlin = GeoPoint::new(52.5200, 13.4050);
+    let potsdam = GeoPoint::new(52.3906, 13.0645);
+    assert!((berlin.distance(&potsdam) - 27.0).abs() < 1.0);
+    let grid = |lat: f64, lon: f64| -> Vec<GeoPoint> {
+        (0..9)
+            .map(|i| {
+                let lat = lat + 0.0003 * (i / 3) as f64;
+                let lon = lon + 0.0003 * (i % 3) as f64;
+                GeoPoint::new(lat, lon)
+            })
+            .collect()
+    };
+    // Both grids span less than 100m and are about 11km apart.
+    let points = flat_vec![grid(52.52, 13.405), grid(52.62, 13.405),];
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 0.1,
+        eps_max: 0.5,
+        pts_min: 5.0,
+        pts_max: 5.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(clusters.len(), 2);
+    assert!(clusters
+        .iter()
+        .flatten()
+        .all(|a| a.category == Category::Core));
+    assert_eq!(
+        defuzzify(points.len(), &clusters),
+        (0..18).map(|i| Some(i / 9)).collect::<Vec<_>>()
+    );
+}
+
+// Similar embeddings should be clustered, whereas orthogonal ones should be noise.
+#[test]
+fn cosine_points() {
+    let points = vec![
+        CosinePoint(vec![1.0, 0.0, 0.0]),
+        CosinePoint(vec![0.99, 0.01, 0.0]),
+        CosinePoint(vec![2.0, 0.02, 0.01]),
+        CosinePoint(vec![0.0, 1.0, 0.0]),
+        CosinePoint(vec![0.0, 0.0, 1.0]),
+        CosinePoint(vec![0.0, 0.0, 0.0]),
+    ];
+    assert_eq!(points[3].distance(&points[4]), 1.0);
+    assert_eq!(points[0].distance(&points[5]), 1.0);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 0.01,
+        eps_max: 0.01,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(
+        defuzzify(points.len(), &clusters),
+        vec![Some(0), Some(0), Some(0), None, None, None]
+    );
+}
+
+// Fixed-size arrays should measure Euclidean distances.
+#[test]
+fn array_points() {
+    assert_eq!([0.0f32, 0.0].distance(&[3.0, 4.0]), 5.0);
+    assert_eq!([1.0, 2.0, 3.0].distance(&[1.0, 2.0, 3.0]), 0.0);
+    let points: Vec<[f32; 128]> = (0..10).map(|i| [i as f32 * 0.1; 128]).collect();
+    let fuzzy_dbscan = FuzzyDBSCAN::builder()
+        .eps_min(2.0)
+        .eps_max(2.0)
+        .pts_min(3.0)
+        .pts_max(3.0)
+        .build()
+        .unwrap();
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(defuzzify(points.len(), &clusters), vec![Some(0); 10]);
+}
+
+// The public intermediate values should match the labels of the clustering.
+#[test]
+fn intermediate_values() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let densities: Vec<f64> = (0..points.len())
+        .map(|i| fuzzy_dbscan.compute_density(&points, i))
+        .collect();
+    assert_eq!(densities, vec![2.0, 2.25, 1.5, 2.25, 2.0, 1.0]);
+    assert_eq!(
+        fuzzy_dbscan.compute_border_label(&points[2], &points[1]),
+        0.25
+    );
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    for a in clusters.iter().flatten() {
+        let core_label = fuzzy_dbscan.compute_core_label(densities[a.index]);
+        match a.category {
+            Category::Core => assert_eq!(a.label, core_label),
+            Category::Border => {
+                assert_eq!(core_label, 0.0);
+                assert_eq!(a.label, 0.25);
+            }
+            Category::Noise => assert_eq!(core_label, 0.0),
+            _ => unreachable!(),
+        }
+    }
+}
+
+// Iterating over clusters should expand the same clusters one at a time.
+#[test]
+fn cluster_iter() {
+    let points = line(&[0.0, 1.0, 2.75, 4.5, 5.5, 10.0]);
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let mut clusters = fuzzy_dbscan.cluster_iter(&points).unwrap();
+    let first = clusters.next().unwrap();
+    assert_eq!(first, fuzzy_dbscan.cluster(&points).unwrap()[0]);
+    assert_eq!(clusters.count(), 2);
+    let points = mixed_gaussian();
+    for min_cluster_size in [1, 10] {
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min: BASE_R / 16.0,
+            eps_max: BASE_R / 8.0,
+            pts_min: 5.0,
+            pts_max: 5.0,
+            min_cluster_size,
+            ..Default::default()
+        };
+        let clusters: Vec<Cluster> = fuzzy_dbscan.cluster_iter(&points).unwrap().collect();
+        assert_eq!(
+            normalized(clusters),
+            normalized(fuzzy_dbscan.cluster(&points).unwrap())
+        );
+    }
+    assert_eq!(
+        fuzzy_dbscan.cluster_iter::<Point1D>(&[]).err(),
+        Some(FuzzyDBSCANError::EmptyInput)
+    );
+}
+
+// Weighting time as a third dimension should separate traces that cross at different times.
+#[test]
+fn spacetime_points() {
+    struct SpacetimePoint {
+        x: f32,
+        y: f32,
+        t: f32,
+        alpha: f32,
+    }
+    impl MetricSpace<f32> for SpacetimePoint {
+        fn distance(&self, other: &Self) -> f32 {
+            let dt = self.alpha * (other.t - self.t);
+            ((other.x - self.x).powi(2) + (other.y - self.y).powi(2) + dt.powi(2)).sqrt()
+        }
+    }
+    // Two cars drive along the same road one minute apart, reporting every second.
+    let trace = |start: f32, alpha: f32| -> Vec<SpacetimePoint> {
+        (0..60)
+            .map(|second| SpacetimePoint {
+                x: second as f32 * 10.0,
+                y: (second % 3) as f32,
+                t: start + second as f32,
+                alpha,
+            })
+            .collect()
+    };
+    let fuzzy_dbscan = FuzzyDBSCAN::<f32> {
+        eps_min: 15.0,
+        eps_max: 30.0,
+        pts_min: 2.0,
+        pts_max: 3.0,
+        ..Default::default()
+    };
+    for (alpha, expected) in [(0.0, 1), (10.0, 2)] {
+        let points: Vec<SpacetimePoint> = [trace(0.0, alpha), trace(60.0, alpha)]
+            .into_iter()
+            .flatten()
+            .collect();
+        let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+        assert_eq!(clusters.cluster_count(), expected);
+        assert!(clusters.noise().is_none());
+        if expected == 2 {
+            let assignments = defuzzify(points.len(), &clusters);
+            let (first, second) = assignments.split_at(60);
+            assert!(first.iter().all(|&cluster_id| cluster_id == first[0]));
+            assert!(second.iter().all(|&cluster_id| cluster_id == second[0]));
+        }
+    }
+}
+
+// Each region query should compute every distance only once.
+#[test]
+fn distance_calls() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    struct CountingPoint(f64);
+    impl MetricSpace for CountingPoint {
+        fn distance(&self, other: &Self) -> f64 {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            (other.0 - self.0).abs()
+        }
+    }
+    let points: Vec<CountingPoint> = (0..10).map(|i| CountingPoint(i as f64)).collect();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 10.0,
+        eps_max: 20.0,
+        pts_min: 2.0,
+        pts_max: 5.0,
+        ..Default::default()
+    };
+    let neighbors = fuzzy_dbscan.region_query_with_distances(&points, 0);
+    assert_eq!(neighbors.len(), 9);
+    assert_eq!(neighbors[2], (3, 3.0, 1.0));
+    assert_eq!(CALLS.swap(0, Ordering::Relaxed), 9);
+    fuzzy_dbscan.cluster(&points).unwrap();
+    // Every point is queried exactly once.
+    assert_eq!(CALLS.swap(0, Ordering::Relaxed), 10 * 9);
+    // Also border points of several clusters and noise points.
+    let points: Vec<CountingPoint> = [0.0, 1.0, 2.75, 4.5, 5.5, 10.0]
+        .iter()
+        .map(|&x| CountingPoint(x))
+        .collect();
+    let fuzzy_dbscan = FuzzyDBSCAN {
+        eps_min: 1.0,
+        eps_max: 2.0,
+        pts_min: 2.0,
+        pts_max: 2.0,
+        ..Default::default()
+    };
+    let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+    assert_eq!(query::border_points(&clusters), vec![2]);
+    assert_eq!(CALLS.load(Ordering::Relaxed), 6 * 5);
+}
+
+proptest! {
+    // Clusterings of random points should only contain non-empty clusters of valid assignments,
+    // which cover all points.
+    #[test]
+    fn random_clusterings(
+        xs in prop::collection::vec(-100.0..100.0f64, 1..50),
+        eps_min in 0.0..10.0f64,
+        eps_range in 0.0..10.0f64,
+        pts_min in 0.0..10.0f64,
+        pts_range in 0.0..10.0f64,
+    ) {
+        let points = line(&xs);
+        let fuzzy_dbscan = FuzzyDBSCAN {
+            eps_min,
+            eps_max: eps_min + eps_range,
+            pts_min,
+            pts_max: pts_min + pts_range,
+            ..Default::default()
+        };
+        let clusters = fuzzy_dbscan.cluster(&points).unwrap();
+        for a in clusters.iter().flatten() {
+            prop_assert!(a.index < points.len());
+            prop_assert!((0.0..=1.0).contains(&a.label), "{:?}", a);
+        }
+        prop_assert!(clusters.iter().all(|cluster| !cluster.is_empty()));
+        let mut indices: Vec<usize> = clusters.iter().flatten().map(|a| a.index).collect();
+        indices.sort_unstable();
+        indices.dedup();
+        prop_assert_eq!(indices, (0..points.len()).collect::<Vec<_>>());
+    }
+
+    // Clusterings with crisp parameters should reduce to DBSCAN, i.e., only have labels of one.
+    #[test]
+    fn random_dbscan_reductions(
+        xs in prop::collection::vec(-100.0..100.0f64, 1..50),
+        eps in 0.0..10.0f64,
+        pts in 0.0..10.0f64,
+    ) {
+        let points = line(&xs);
+        let clusters = FuzzyDBSCAN::with_mode(ClusteringMode::Crisp, eps, pts)
+            .cluster(&points)
+            .unwrap();
+        prop_assert!(clusters.iter().flatten().all(|a| a.label == 1.0));
+    }
 }
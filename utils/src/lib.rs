@@ -2,9 +2,9 @@ extern crate fuzzy_dbscan;
 extern crate rand;
 extern crate svg;
 
-use fuzzy_dbscan::{Category, Cluster, MetricSpace};
+use fuzzy_dbscan::{Category, Cluster, Coordinates, MetricSpace};
 use rand::rngs::StdRng;
-use rand::SeedableRng;
+use rand::{Rng, SeedableRng};
 use rand_distr::{Distribution, Normal};
 use std::f64;
 use svg::node::element::{Circle, Definitions, RadialGradient, Stop, Title};
@@ -25,22 +25,50 @@ pub struct Point {
 
 impl MetricSpace for Point {
     fn distance(&self, other: &Self) -> f64 {
-        ((other.x - self.x).powi(2) + (other.y - self.y).powi(2)).sqrt()
+        self.distance_squared(other).sqrt()
+    }
+
+    fn distance_squared(&self, other: &Self) -> f64 {
+        (other.x - self.x).powi(2) + (other.y - self.y).powi(2)
+    }
+}
+
+impl Coordinates<2> for Point {
+    fn coordinates(&self) -> [f64; 2] {
+        [self.x, self.y]
     }
 }
 
 pub fn gaussian_circle(n: usize, cx: f64, cy: f64, r: f64) -> Vec<Point> {
     let center = Point { x: cx, y: cy };
     let sigma = r / 3.0;
-    let normal_x = Normal::new(cx as f64, sigma as f64).unwrap();
-    let normal_y = Normal::new(cy as f64, sigma as f64).unwrap();
-    let mut random: _ = StdRng::seed_from_u64(1337);
+    let normal_x = Normal::new(cx, sigma).unwrap();
+    let normal_y = Normal::new(cy, sigma).unwrap();
+    let mut random = StdRng::seed_from_u64(1337);
+    let mut points = Vec::new();
+    let mut c = 0;
+    while c < n {
+        let sample = Point {
+            x: normal_x.sample(&mut random),
+            y: normal_y.sample(&mut random),
+        };
+        if center.distance(&sample) <= r {
+            points.push(sample);
+            c += 1;
+        }
+    }
+    points
+}
+
+pub fn uniform_circle(n: usize, cx: f64, cy: f64, r: f64) -> Vec<Point> {
+    let center = Point { x: cx, y: cy };
+    let mut random = StdRng::seed_from_u64(1337);
     let mut points = Vec::new();
     let mut c = 0;
     while c < n {
         let sample = Point {
-            x: normal_x.sample(&mut random) as f64,
-            y: normal_y.sample(&mut random) as f64,
+            x: random.gen_range(cx - r..=cx + r),
+            y: random.gen_range(cy - r..=cy + r),
         };
         if center.distance(&sample) <= r {
             points.push(sample);
@@ -50,6 +78,81 @@ pub fn gaussian_circle(n: usize, cx: f64, cy: f64, r: f64) -> Vec<Point> {
     points
 }
 
+pub fn ring(n: usize, cx: f64, cy: f64, r_inner: f64, r_outer: f64) -> Vec<Point> {
+    annulus(n, cx, cy, r_inner, r_outer, |_| true)
+}
+
+pub fn crescent(n: usize, cx: f64, cy: f64, r_inner: f64, r_outer: f64) -> Vec<Point> {
+    annulus(n, cx, cy, r_inner, r_outer, |sample| sample.y >= cy)
+}
+
+fn annulus<F: Fn(&Point) -> bool>(
+    n: usize,
+    cx: f64,
+    cy: f64,
+    r_inner: f64,
+    r_outer: f64,
+    accept: F,
+) -> Vec<Point> {
+    let center = Point { x: cx, y: cy };
+    let mut random = StdRng::seed_from_u64(1337);
+    let mut points = Vec::new();
+    let mut c = 0;
+    while c < n {
+        let sample = Point {
+            x: random.gen_range(cx - r_outer..=cx + r_outer),
+            y: random.gen_range(cy - r_outer..=cy + r_outer),
+        };
+        let distance = center.distance(&sample);
+        if distance >= r_inner && distance <= r_outer && accept(&sample) {
+            points.push(sample);
+            c += 1;
+        }
+    }
+    points
+}
+
+pub fn spiral(n: usize, turns: f64, noise: f64) -> Vec<Point> {
+    rotated_spiral(n, turns, noise, 0.0)
+}
+
+pub fn two_spirals(n: usize) -> (Vec<Point>, Vec<Point>) {
+    (
+        rotated_spiral(n, 2.0, 0.2, 0.0),
+        rotated_spiral(n, 2.0, 0.2, f64::consts::PI),
+    )
+}
+
+fn rotated_spiral(n: usize, turns: f64, noise: f64, rotation: f64) -> Vec<Point> {
+    let normal = Normal::new(0.0, noise).unwrap();
+    let mut random = StdRng::seed_from_u64(1337);
+    // The square root spaces points roughly evenly along the arc, whose length grows with the
+    // angle. The radius starts at half the distance between turns to keep the center sparse.
+    (0..n)
+        .map(|i| {
+            let angle = turns * 2.0 * f64::consts::PI * ((i as f64 + 0.5) / n as f64).sqrt();
+            let radius = angle + f64::consts::PI;
+            Point {
+                x: radius * (angle + rotation).cos() + normal.sample(&mut random),
+                y: radius * (angle + rotation).sin() + normal.sample(&mut random),
+            }
+        })
+        .collect()
+}
+
+pub fn grid_points(rows: usize, cols: usize, spacing: f64) -> Vec<Point> {
+    let mut points = Vec::new();
+    for row in 0..rows {
+        for col in 0..cols {
+            points.push(Point {
+                x: col as f64 * spacing,
+                y: row as f64 * spacing,
+            });
+        }
+    }
+    points
+}
+
 pub fn dump_svg(name: &str, points: &[Point], clusters: &[Cluster]) {
     let (min_x, min_y, max_x, max_y) = points.iter().cloned().fold(
         (f64::MAX, f64::MAX, f64::MIN, f64::MIN),